@@ -2,13 +2,258 @@
 //!
 //! This module provides functions for reading and processing audio files
 //! to prepare them for transcription engines.
+//!
+//! All engines expect 16 kHz mono `f32` samples. By default the loaders in
+//! this module convert whatever they are given into that layout: multi-channel
+//! audio is downmixed by averaging the channels and other sample rates are
+//! converted with an FFT-based resampler. Callers that rely on the input
+//! already being in the engine format can opt into strict validation with
+//! [`AudioReadParams::strict`].
+//!
+//! # Compressed Formats
+//!
+//! With the `codecs` cargo feature enabled, [`read_audio_samples`] also decodes
+//! FLAC, Ogg/Vorbis, MP3 and AAC (M4A/MP4) files using pure-Rust decoders.
+//! The container is detected from the file header rather than the extension.
+//! Opus is not supported: there is no pure-Rust Opus decoder to build on, so
+//! Ogg/Opus files are rejected with [`TranscribeError::UnsupportedAudioFormat`]
+//! and need converting first.
 
+use crate::TranscribeError;
+use rubato::{FftFixedIn, Resampler};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::Path;
 
+/// Sample rate expected by all transcription engines.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// Number of input frames handed to the resampler per processing call.
+const RESAMPLER_CHUNK_SIZE: usize = 1024;
+
+/// Parameters for configuring how audio files are loaded.
+#[derive(Debug, Clone, Default)]
+pub struct AudioReadParams {
+    /// Reject files that are not already 16 kHz, 16-bit, mono PCM instead of
+    /// converting them. Defaults to `false`.
+    pub strict: bool,
+}
+
+impl AudioReadParams {
+    /// Create parameters that reject any file not already in the engine format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::audio::AudioReadParams;
+    ///
+    /// let params = AudioReadParams::strict();
+    /// assert!(params.strict);
+    /// ```
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+}
+
+/// Audio container formats recognised by [`detect_container`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioContainer {
+    /// RIFF/WAVE file
+    Wav,
+    /// Native FLAC stream
+    Flac,
+    /// Ogg container (Vorbis or Opus)
+    Ogg,
+    /// MPEG audio, optionally prefixed with an ID3v2 tag
+    Mp3,
+    /// ISO base media file (M4A, MP4) carrying AAC
+    Mp4,
+    /// Anything else
+    Unknown,
+}
+
+/// Detect the container format of an audio file from its leading bytes.
+///
+/// At least the first 12 bytes of the file should be provided; shorter
+/// headers are detected on a best-effort basis.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::audio::{detect_container, AudioContainer};
+///
+/// assert_eq!(detect_container(b"fLaC\0\0\0\x22"), AudioContainer::Flac);
+/// assert_eq!(detect_container(b"RIFF\x24\0\0\0WAVE"), AudioContainer::Wav);
+/// ```
+pub fn detect_container(header: &[u8]) -> AudioContainer {
+    if header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WAVE" {
+        AudioContainer::Wav
+    } else if header.starts_with(b"fLaC") {
+        AudioContainer::Flac
+    } else if header.starts_with(b"OggS") {
+        AudioContainer::Ogg
+    } else if header.len() >= 8 && &header[4..8] == b"ftyp" {
+        AudioContainer::Mp4
+    } else if header.starts_with(b"ID3")
+        || (header.len() >= 2 && header[0] == 0xFF && header[1] & 0xE0 == 0xE0)
+    {
+        AudioContainer::Mp3
+    } else {
+        AudioContainer::Unknown
+    }
+}
+
+/// Read an audio file of any supported format and convert it to 16 kHz mono.
+///
+/// WAV files are read with [`read_wav_samples`]. Other containers require the
+/// `codecs` cargo feature; without it they are rejected with an error.
+///
+/// # Arguments
+///
+/// * `audio_path` - Path to the audio file to read
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use transcribe_rs::audio::read_audio_samples;
+/// use std::path::Path;
+///
+/// let samples = read_audio_samples(Path::new("meeting.m4a"))?;
+/// println!("Loaded {} samples", samples.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_audio_samples(audio_path: &Path) -> Result<Vec<f32>, TranscribeError> {
+    read_audio_samples_with_params(audio_path, AudioReadParams::default())
+}
+
+/// Read an audio file of any supported format with custom loading parameters.
+///
+/// In strict mode only WAV files already in the engine format are accepted.
+pub fn read_audio_samples_with_params(
+    audio_path: &Path,
+    params: AudioReadParams,
+) -> Result<Vec<f32>, TranscribeError> {
+    let mut header = Vec::with_capacity(12);
+    File::open(audio_path)?.take(12).read_to_end(&mut header)?;
+
+    match detect_container(&header) {
+        AudioContainer::Wav => read_wav_samples_with_params(audio_path, params),
+        container if params.strict => Err(TranscribeError::unsupported_audio(format!(
+            "Expected a 16 kHz mono 16-bit WAV file, found {:?} container",
+            container
+        ))),
+        #[cfg(feature = "codecs")]
+        _ => decode_compressed_samples(audio_path),
+        #[cfg(not(feature = "codecs"))]
+        AudioContainer::Unknown => Err(TranscribeError::unsupported_audio(
+            "Unrecognised audio container",
+        )),
+        #[cfg(not(feature = "codecs"))]
+        container => Err(TranscribeError::unsupported_audio(format!(
+            "Decoding {:?} audio requires the `codecs` feature",
+            container
+        ))),
+    }
+}
+
+/// Decode a compressed audio file with symphonia and convert it to 16 kHz mono.
+#[cfg(feature = "codecs")]
+fn decode_compressed_samples(audio_path: &Path) -> Result<Vec<f32>, TranscribeError> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL, CODEC_TYPE_OPUS};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = File::open(audio_path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = audio_path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| TranscribeError::unsupported_audio("No audio track found"))?;
+
+    if track.codec_params.codec == CODEC_TYPE_OPUS {
+        return Err(TranscribeError::unsupported_audio(
+            "Opus audio is not supported; convert it to WAV, FLAC or Vorbis",
+        ));
+    }
+
+    let track_id = track.id;
+    let sample_rate = track.codec_params.sample_rate.ok_or_else(|| {
+        TranscribeError::unsupported_audio("Audio track does not declare a sample rate")
+    })?;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut samples = Vec::new();
+    let mut sample_buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(err)) => {
+                log::warn!("Skipping corrupt audio packet: {}", err);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // Downmix each packet with its own layout, since the channel count
+        // may change partway through a stream
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+
+        let buffer = match &mut sample_buffer {
+            Some(buffer) if buffer.capacity() >= decoded.capacity() * channels => buffer,
+            _ => sample_buffer.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend(downmix_to_mono(buffer.samples(), channels));
+    }
+
+    resample(&samples, sample_rate, TARGET_SAMPLE_RATE)
+}
+
 /// Read WAV file samples and convert them to the required format.
 ///
 /// This function reads a WAV file and converts it to the format expected by
-/// transcription engines: 16kHz sample rate, 16-bit samples, mono channel.
+/// transcription engines: 16kHz sample rate, mono channel. Files with a
+/// different sample rate or channel count are resampled and downmixed.
+///
+/// All bit depths supported by `hound` are accepted: 8, 16, 24 and 32-bit
+/// integer PCM as well as 32-bit IEEE float.
 ///
 /// # Arguments
 ///
@@ -22,7 +267,7 @@ use std::path::Path;
 ///
 /// This function will return an error if:
 /// - The file cannot be opened or read
-/// - The WAV format is incorrect (not 16kHz, 16-bit, mono)
+/// - The sample format is not supported (e.g. 64-bit float)
 /// - The samples cannot be converted to the expected format
 ///
 /// # Examples
@@ -35,67 +280,663 @@ use std::path::Path;
 /// println!("Loaded {} samples", samples.len());
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
+pub fn read_wav_samples(wav_path: &Path) -> Result<Vec<f32>, TranscribeError> {
+    read_wav_samples_with_params(wav_path, AudioReadParams::default())
+}
+
+/// Read WAV file samples with custom loading parameters.
+///
+/// With the default parameters this behaves like [`read_wav_samples`]. When
+/// [`AudioReadParams::strict`] is set, the file must already be in the engine
+/// format and no conversion is performed.
+///
+/// # Arguments
+///
+/// * `wav_path` - Path to the WAV file to read
+/// * `params` - Loading parameters
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use transcribe_rs::audio::{read_wav_samples_with_params, AudioReadParams};
+/// use std::path::Path;
+///
+/// // Fail instead of resampling if the file is not 16 kHz mono
+/// let samples = read_wav_samples_with_params(Path::new("audio.wav"), AudioReadParams::strict())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 ///
-/// # Audio Requirements
+/// # Strict Mode Requirements
 ///
-/// The input WAV file must have:
+/// In strict mode the input WAV file must have:
 /// - Sample rate: 16,000 Hz
 /// - Bit depth: 16 bits per sample
 /// - Channels: 1 (mono)
 /// - Format: PCM integer samples
-pub fn read_wav_samples(wav_path: &Path) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+pub fn read_wav_samples_with_params(
+    wav_path: &Path,
+    params: AudioReadParams,
+) -> Result<Vec<f32>, TranscribeError> {
     let mut reader = hound::WavReader::open(wav_path)?;
     let spec = reader.spec();
 
+    if params.strict {
+        validate_strict_spec(&spec)?;
+    }
+
+    let samples = decode_wav_samples(&mut reader)?;
+
+    let mono = downmix_to_mono(&samples, spec.channels as usize);
+    resample(&mono, spec.sample_rate, TARGET_SAMPLE_RATE)
+}
+
+/// Decode every sample in the WAV stream to `f32` in the range [-1.0, 1.0].
+fn decode_wav_samples<R: Read>(
+    reader: &mut hound::WavReader<R>,
+) -> Result<Vec<f32>, TranscribeError> {
+    let mut samples = Vec::with_capacity(reader.len() as usize);
+    read_wav_block(reader, usize::MAX, &mut samples)?;
+    Ok(samples)
+}
+
+/// Decode up to `max_samples` interleaved samples from the WAV stream into `out`.
+///
+/// Integer samples are scaled by the positive full-scale value of their bit
+/// depth, with the most negative value mapped to exactly -1.0. Float samples
+/// are clamped to the normalized range.
+///
+/// Returns `false` once the end of the stream has been reached.
+fn read_wav_block<R: Read>(
+    reader: &mut hound::WavReader<R>,
+    max_samples: usize,
+    out: &mut Vec<f32>,
+) -> Result<bool, TranscribeError> {
+    let spec = reader.spec();
+    let start_len = out.len();
+
+    match (spec.sample_format, spec.bits_per_sample) {
+        (hound::SampleFormat::Int, bits @ (8 | 16 | 24 | 32)) => {
+            let min = -(1i64 << (bits - 1));
+            let scale = ((1i64 << (bits - 1)) - 1) as f32;
+
+            for sample in reader.samples::<i32>().take(max_samples) {
+                let s = sample?;
+                out.push(if s as i64 == min {
+                    -1.0
+                } else {
+                    s as f32 / scale
+                });
+            }
+        }
+        (hound::SampleFormat::Float, 32) => {
+            for sample in reader.samples::<f32>().take(max_samples) {
+                out.push(sample?.clamp(-1.0, 1.0));
+            }
+        }
+        (format, bits) => {
+            return Err(TranscribeError::UnsupportedAudioFormat {
+                reason: format!("{bits} bit {format:?} samples are not supported"),
+                spec: Some(spec),
+            })
+        }
+    }
+
+    Ok(out.len() - start_len == max_samples)
+}
+
+fn validate_strict_spec(spec: &hound::WavSpec) -> Result<(), TranscribeError> {
     let expected_spec = hound::WavSpec {
         channels: 1,
-        sample_rate: 16000,
+        sample_rate: TARGET_SAMPLE_RATE,
         bits_per_sample: 16,
         sample_format: hound::SampleFormat::Int,
     };
 
-    if spec.channels != expected_spec.channels {
-        return Err(format!(
+    let reason = if spec.channels != expected_spec.channels {
+        format!(
             "Expected {} channels, found {}",
             expected_spec.channels, spec.channels
         )
-        .into());
-    }
-
-    if spec.sample_rate != expected_spec.sample_rate {
-        return Err(format!(
+    } else if spec.sample_rate != expected_spec.sample_rate {
+        format!(
             "Expected {} Hz sample rate, found {} Hz",
             expected_spec.sample_rate, spec.sample_rate
         )
-        .into());
-    }
-
-    if spec.bits_per_sample != expected_spec.bits_per_sample {
-        return Err(format!(
+    } else if spec.bits_per_sample != expected_spec.bits_per_sample {
+        format!(
             "Expected {} bits per sample, found {}",
             expected_spec.bits_per_sample, spec.bits_per_sample
         )
-        .into());
+    } else if spec.sample_format != expected_spec.sample_format {
+        format!("Expected Int sample format, found {:?}", spec.sample_format)
+    } else {
+        return Ok(());
+    };
+
+    Err(TranscribeError::UnsupportedAudioFormat {
+        reason,
+        spec: Some(*spec),
+    })
+}
+
+/// Downmix interleaved multi-channel samples to mono by averaging each frame.
+///
+/// Mono input is returned unchanged. A trailing partial frame is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::audio::downmix_to_mono;
+///
+/// let stereo = [1.0, 0.0, 0.5, 0.5];
+/// assert_eq!(downmix_to_mono(&stereo, 2), vec![0.5, 0.5]);
+/// ```
+pub fn downmix_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resample mono audio from one sample rate to another.
+///
+/// Uses a synchronous FFT resampler with anti-aliasing, which is well suited
+/// to the fixed integer rates produced by recorders (44.1 kHz, 48 kHz, ...).
+/// The output is aligned with the input (the resampler delay is removed) and
+/// has `ceil(len * to_rate / from_rate)` samples.
+///
+/// # Errors
+///
+/// Returns an error if either sample rate is zero or the resampler fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::audio::resample;
+///
+/// let input = vec![0.0; 48_000];
+/// let output = resample(&input, 48_000, 16_000)?;
+/// assert_eq!(output.len(), 16_000);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn resample(
+    samples: &[f32],
+    from_rate: u32,
+    to_rate: u32,
+) -> Result<Vec<f32>, TranscribeError> {
+    let mut converter = StreamConverter::new(from_rate, to_rate, 1)?;
+    converter.push(samples)?;
+    converter.finish()?;
+    Ok(converter.take(usize::MAX))
+}
+
+/// Incremental downmixer and resampler shared by [`resample`] and the
+/// streaming [`AudioSource`] implementations.
+///
+/// Interleaved input is downmixed to mono, fed through the resampler in
+/// fixed-size chunks and buffered until taken. The resampler delay is
+/// removed so the output lines up with the input.
+struct StreamConverter {
+    channels: usize,
+    from_rate: u32,
+    to_rate: u32,
+    resampler: Option<FftFixedIn<f32>>,
+    pending_input: Vec<f32>,
+    output: VecDeque<f32>,
+    delay_remaining: usize,
+    input_total: u64,
+    output_total: u64,
+    finished: bool,
+}
+
+impl StreamConverter {
+    fn new(from_rate: u32, to_rate: u32, channels: usize) -> Result<Self, TranscribeError> {
+        if from_rate == 0 || to_rate == 0 {
+            return Err(TranscribeError::unsupported_audio(format!(
+                "Invalid sample rate conversion {from_rate} Hz -> {to_rate} Hz"
+            )));
+        }
+        if channels == 0 {
+            return Err(TranscribeError::unsupported_audio(
+                "Audio must have at least one channel",
+            ));
+        }
+
+        let resampler = if from_rate == to_rate {
+            None
+        } else {
+            Some(FftFixedIn::<f32>::new(
+                from_rate as usize,
+                to_rate as usize,
+                RESAMPLER_CHUNK_SIZE,
+                1,
+                1,
+            )?)
+        };
+        let delay_remaining = resampler
+            .as_ref()
+            .map(|resampler| resampler.output_delay())
+            .unwrap_or(0);
+
+        Ok(Self {
+            channels,
+            from_rate,
+            to_rate,
+            resampler,
+            pending_input: Vec::new(),
+            output: VecDeque::new(),
+            delay_remaining,
+            input_total: 0,
+            output_total: 0,
+            finished: false,
+        })
+    }
+
+    /// Number of converted samples ready to be taken.
+    fn available(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Whether [`StreamConverter::finish`] has been called.
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Feed interleaved samples at the source rate and channel count.
+    fn push(&mut self, interleaved: &[f32]) -> Result<(), TranscribeError> {
+        let mono = downmix_to_mono(interleaved, self.channels);
+        self.input_total += mono.len() as u64;
+
+        let Some(resampler) = self.resampler.as_mut() else {
+            self.output.extend(mono);
+            return Ok(());
+        };
+
+        self.pending_input.extend_from_slice(&mono);
+        let chunk_size = resampler.input_frames_next();
+        let mut consumed = 0;
+        while self.pending_input.len() - consumed >= chunk_size {
+            let chunk = &self.pending_input[consumed..consumed + chunk_size];
+            let processed = resampler.process(&[chunk], None)?;
+            consumed += chunk_size;
+            Self::emit(
+                &mut self.output,
+                &mut self.delay_remaining,
+                &mut self.output_total,
+                &processed[0],
+            );
+        }
+        self.pending_input.drain(..consumed);
+
+        Ok(())
+    }
+
+    /// Flush the remaining input and the resampler delay line.
+    ///
+    /// After this call the total output length is exactly
+    /// `ceil(input_len * to_rate / from_rate)` samples.
+    fn finish(&mut self) -> Result<(), TranscribeError> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        let expected_total =
+            (self.input_total * self.to_rate as u64).div_ceil(self.from_rate as u64);
+
+        let Some(resampler) = self.resampler.as_mut() else {
+            return Ok(());
+        };
+
+        if !self.pending_input.is_empty() {
+            let processed = resampler.process_partial(Some(&[&self.pending_input[..]]), None)?;
+            self.pending_input.clear();
+            Self::emit(
+                &mut self.output,
+                &mut self.delay_remaining,
+                &mut self.output_total,
+                &processed[0],
+            );
+        }
+
+        // Push zeros through until the delayed tail of the signal has been emitted
+        while self.output_total < expected_total {
+            let processed = resampler.process_partial::<&[f32]>(None, None)?;
+            Self::emit(
+                &mut self.output,
+                &mut self.delay_remaining,
+                &mut self.output_total,
+                &processed[0],
+            );
+        }
+
+        let excess = (self.output_total - expected_total) as usize;
+        self.output
+            .truncate(self.output.len().saturating_sub(excess));
+        self.output_total = expected_total;
+
+        Ok(())
+    }
+
+    /// Take up to `max` converted samples from the front of the buffer.
+    fn take(&mut self, max: usize) -> Vec<f32> {
+        let count = max.min(self.output.len());
+        self.output.drain(..count).collect()
+    }
+
+    fn emit(
+        output: &mut VecDeque<f32>,
+        delay_remaining: &mut usize,
+        output_total: &mut u64,
+        samples: &[f32],
+    ) {
+        let skip = (*delay_remaining).min(samples.len());
+        *delay_remaining -= skip;
+        output.extend(&samples[skip..]);
+        *output_total += (samples.len() - skip) as u64;
+    }
+}
+
+/// Default number of samples per frame yielded by the [`AudioSource`]
+/// implementations in this module (100 ms at 16 kHz).
+pub const DEFAULT_FRAME_SIZE: usize = 1_600;
+
+/// Number of source frames read per refill of a converting source.
+const SOURCE_READ_FRAMES: usize = 4_096;
+
+/// A stream of 16 kHz mono audio delivered in fixed-size frames.
+///
+/// Sources let long recordings be processed without holding the whole file
+/// in memory. Every frame except the last contains exactly
+/// [`AudioSource::frame_size`] samples; the last frame may be shorter.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::audio::{AudioSource, BufferSource};
+///
+/// let mut source = BufferSource::new(vec![0.0; 2_500], 1_000);
+/// let mut lengths = Vec::new();
+/// while let Some(frame) = source.next_frame()? {
+///     lengths.push(frame.len());
+/// }
+/// assert_eq!(lengths, vec![1_000, 1_000, 500]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait AudioSource {
+    /// Number of samples in each full frame.
+    fn frame_size(&self) -> usize;
+
+    /// Read the next frame of 16 kHz mono samples normalized to [-1.0, 1.0].
+    ///
+    /// Returns `Ok(None)` once the source is exhausted.
+    fn next_frame(&mut self) -> Result<Option<Vec<f32>>, TranscribeError>;
+}
+
+/// Pull frames out of a converter, refilling it from `read_block` as needed.
+///
+/// `read_block` appends interleaved source samples and returns `false` once
+/// the underlying stream is exhausted.
+fn next_converted_frame(
+    converter: &mut StreamConverter,
+    frame_size: usize,
+    mut read_block: impl FnMut(&mut Vec<f32>) -> Result<bool, TranscribeError>,
+) -> Result<Option<Vec<f32>>, TranscribeError> {
+    let mut block = Vec::new();
+
+    while converter.available() < frame_size && !converter.is_finished() {
+        block.clear();
+        let more = read_block(&mut block)?;
+        converter.push(&block)?;
+        if !more {
+            converter.finish()?;
+        }
+    }
+
+    if converter.available() == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(converter.take(frame_size)))
+    }
+}
+
+/// An [`AudioSource`] over samples already held in memory.
+///
+/// The samples are expected to be 16 kHz mono; no conversion is performed.
+pub struct BufferSource {
+    samples: Vec<f32>,
+    position: usize,
+    frame_size: usize,
+}
+
+impl BufferSource {
+    /// Create a source that yields `samples` in frames of `frame_size`.
+    pub fn new(samples: Vec<f32>, frame_size: usize) -> Self {
+        Self {
+            samples,
+            position: 0,
+            frame_size: frame_size.max(1),
+        }
+    }
+}
+
+impl AudioSource for BufferSource {
+    fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    fn next_frame(&mut self) -> Result<Option<Vec<f32>>, TranscribeError> {
+        if self.position >= self.samples.len() {
+            return Ok(None);
+        }
+
+        let end = (self.position + self.frame_size).min(self.samples.len());
+        let frame = self.samples[self.position..end].to_vec();
+        self.position = end;
+        Ok(Some(frame))
+    }
+}
+
+/// An [`AudioSource`] that streams a WAV file from disk.
+///
+/// Accepts the same sample formats as [`read_wav_samples`] and resamples and
+/// downmixes on the fly, so only a few frames are held in memory at a time.
+pub struct WavFileSource {
+    reader: hound::WavReader<BufReader<File>>,
+    converter: StreamConverter,
+    frame_size: usize,
+}
+
+impl WavFileSource {
+    /// Open a WAV file and stream it in frames of `frame_size` samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use transcribe_rs::audio::{AudioSource, WavFileSource, DEFAULT_FRAME_SIZE};
+    /// use std::path::Path;
+    ///
+    /// let mut source = WavFileSource::open(Path::new("long-meeting.wav"), DEFAULT_FRAME_SIZE)?;
+    /// while let Some(frame) = source.next_frame()? {
+    ///     println!("Read {} samples", frame.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open(wav_path: &Path, frame_size: usize) -> Result<Self, TranscribeError> {
+        let reader = hound::WavReader::open(wav_path)?;
+        let spec = reader.spec();
+
+        match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Int, 8 | 16 | 24 | 32) | (hound::SampleFormat::Float, 32) => {}
+            (format, bits) => {
+                return Err(TranscribeError::UnsupportedAudioFormat {
+                    reason: format!("{bits} bit {format:?} samples are not supported"),
+                    spec: Some(spec),
+                })
+            }
+        }
+
+        let converter =
+            StreamConverter::new(spec.sample_rate, TARGET_SAMPLE_RATE, spec.channels as usize)?;
+
+        Ok(Self {
+            reader,
+            converter,
+            frame_size: frame_size.max(1),
+        })
+    }
+}
+
+impl AudioSource for WavFileSource {
+    fn frame_size(&self) -> usize {
+        self.frame_size
     }
 
-    if spec.sample_format != expected_spec.sample_format {
-        return Err(format!("Expected Int sample format, found {:?}", spec.sample_format).into());
+    fn next_frame(&mut self) -> Result<Option<Vec<f32>>, TranscribeError> {
+        let block_len = SOURCE_READ_FRAMES * self.reader.spec().channels as usize;
+        let reader = &mut self.reader;
+        next_converted_frame(&mut self.converter, self.frame_size, |block| {
+            read_wav_block(reader, block_len, block)
+        })
     }
+}
 
-    let scale = i16::MAX as f32;
+/// Sample encoding of a raw PCM stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcmEncoding {
+    /// Signed 16-bit little-endian integers
+    I16Le,
+    /// 32-bit little-endian IEEE floats
+    F32Le,
+}
 
-    let samples: Result<Vec<f32>, _> = reader
-        .samples::<i16>()
-        .map(|sample| {
-            sample.map(|s| {
+impl PcmEncoding {
+    fn bytes_per_sample(self) -> usize {
+        match self {
+            PcmEncoding::I16Le => 2,
+            PcmEncoding::F32Le => 4,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            PcmEncoding::I16Le => {
+                let s = i16::from_le_bytes([bytes[0], bytes[1]]);
                 if s == i16::MIN {
                     -1.0
                 } else {
-                    s as f32 / scale
+                    s as f32 / i16::MAX as f32
                 }
-            })
+            }
+            PcmEncoding::F32Le => {
+                f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).clamp(-1.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Layout of a raw, headerless PCM stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    /// Sample rate of the stream in Hz
+    pub sample_rate: u32,
+    /// Number of interleaved channels
+    pub channels: u16,
+    /// Encoding of each sample
+    pub encoding: PcmEncoding,
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self {
+            sample_rate: TARGET_SAMPLE_RATE,
+            channels: 1,
+            encoding: PcmEncoding::I16Le,
+        }
+    }
+}
+
+/// An [`AudioSource`] over raw interleaved PCM read from any [`std::io::Read`].
+///
+/// Useful for pipes, sockets and capture devices that deliver headerless
+/// audio. The stream is resampled and downmixed according to its [`PcmFormat`].
+/// A trailing partial sample at the end of the stream is ignored.
+pub struct PcmReaderSource<R: Read> {
+    reader: R,
+    format: PcmFormat,
+    converter: StreamConverter,
+    frame_size: usize,
+    /// Read buffer holding `SOURCE_READ_FRAMES` interleaved frames
+    bytes: Vec<u8>,
+}
+
+impl<R: Read> PcmReaderSource<R> {
+    /// Wrap `reader` as a source yielding frames of `frame_size` samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::audio::{AudioSource, PcmEncoding, PcmFormat, PcmReaderSource};
+    ///
+    /// let bytes: Vec<u8> = [i16::MAX, 0].iter().flat_map(|s| s.to_le_bytes()).collect();
+    /// let format = PcmFormat { sample_rate: 16_000, channels: 1, encoding: PcmEncoding::I16Le };
+    /// let mut source = PcmReaderSource::new(bytes.as_slice(), format, 1_600)?;
+    /// assert_eq!(source.next_frame()?, Some(vec![1.0, 0.0]));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(reader: R, format: PcmFormat, frame_size: usize) -> Result<Self, TranscribeError> {
+        let converter = StreamConverter::new(
+            format.sample_rate,
+            TARGET_SAMPLE_RATE,
+            format.channels as usize,
+        )?;
+        let frame_bytes = format.encoding.bytes_per_sample() * format.channels as usize;
+
+        Ok(Self {
+            reader,
+            format,
+            converter,
+            frame_size: frame_size.max(1),
+            bytes: vec![0; SOURCE_READ_FRAMES * frame_bytes],
         })
-        .collect();
+    }
+}
 
-    Ok(samples?)
+impl<R: Read> AudioSource for PcmReaderSource<R> {
+    fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    fn next_frame(&mut self) -> Result<Option<Vec<f32>>, TranscribeError> {
+        let sample_bytes = self.format.encoding.bytes_per_sample();
+        let frame_bytes = sample_bytes * self.format.channels as usize;
+        let bytes = &mut self.bytes;
+        let reader = &mut self.reader;
+        let encoding = self.format.encoding;
+
+        next_converted_frame(&mut self.converter, self.frame_size, |block| {
+            let mut filled = 0;
+            while filled < bytes.len() {
+                match reader.read(&mut bytes[filled..]) {
+                    Ok(0) => break,
+                    Ok(n) => filled += n,
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(err.into()),
+                }
+            }
+
+            // Only hand whole multi-channel frames to the converter
+            let usable = filled - filled % frame_bytes;
+            block.extend(
+                bytes[..usable]
+                    .chunks_exact(sample_bytes)
+                    .map(|b| encoding.decode(b)),
+            );
+            Ok(filled == bytes.len())
+        })
+    }
 }
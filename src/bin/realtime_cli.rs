@@ -1,13 +1,11 @@
 use std::io::{self, BufRead, Write};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use clap::{Parser, ValueEnum};
-#[cfg(feature = "parakeet")]
-use transcribe_rs::engines::parakeet::{ParakeetEngine, ParakeetInferenceParams};
 use transcribe_rs::{
-    engines::whisper::{WhisperEngine, WhisperInferenceParams},
-    realtime::{InboundMessage, OutboundMessage, RealtimeSession, RealtimeTranscriber},
-    TranscriptionEngine, TranscriptionResult,
+    engines::EngineKind,
+    realtime::{ConfiguredTranscriber, InboundMessage, OutboundMessage, RealtimeSession},
+    TranscribeOptions, TranscriptionTask,
 };
 
 #[derive(Parser, Debug)]
@@ -16,9 +14,9 @@ use transcribe_rs::{
     version
 )]
 struct Args {
-    /// Which engine to use for transcription
-    #[arg(long, value_enum, default_value_t = EngineChoice::Whisper)]
-    engine: EngineChoice,
+    /// Which engine to use for transcription (whisper or parakeet)
+    #[arg(long, default_value = "whisper")]
+    engine: EngineKind,
 
     /// Path to the model file (Whisper) or directory (Parakeet)
     #[arg(long)]
@@ -27,60 +25,23 @@ struct Args {
     /// Optional forced language code passed to Whisper (e.g. "en")
     #[arg(long)]
     language: Option<String>,
-}
 
-#[derive(Copy, Clone, Debug, ValueEnum)]
-enum EngineChoice {
-    Whisper,
-    #[cfg(feature = "parakeet")]
-    Parakeet,
+    /// Transcribe speech as spoken, or translate it to English (Whisper only)
+    #[arg(long, value_enum, default_value_t = TaskChoice::Transcribe)]
+    task: TaskChoice,
 }
 
-impl EngineChoice {
-    fn create_engine(self) -> EngineWrapper {
-        match self {
-            EngineChoice::Whisper => EngineWrapper::Whisper(WhisperEngine::new()),
-            #[cfg(feature = "parakeet")]
-            EngineChoice::Parakeet => EngineWrapper::Parakeet(ParakeetEngine::new()),
-        }
-    }
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
+enum TaskChoice {
+    Transcribe,
+    Translate,
 }
 
-enum EngineWrapper {
-    Whisper(WhisperEngine),
-    #[cfg(feature = "parakeet")]
-    Parakeet(ParakeetEngine),
-}
-
-impl EngineWrapper {
-    fn load_model(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-        match self {
-            EngineWrapper::Whisper(engine) => engine.load_model(path),
-            #[cfg(feature = "parakeet")]
-            EngineWrapper::Parakeet(engine) => engine.load_model(path),
-        }
-    }
-}
-
-impl RealtimeTranscriber for EngineWrapper {
-    fn transcribe(
-        &mut self,
-        samples: Vec<f32>,
-        language: Option<&str>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
-        match self {
-            EngineWrapper::Whisper(engine) => {
-                let mut params = WhisperInferenceParams::default();
-                if let Some(code) = language {
-                    params.language = Some(code.to_string());
-                }
-                engine.transcribe_samples(samples, Some(params))
-            }
-            #[cfg(feature = "parakeet")]
-            EngineWrapper::Parakeet(engine) => {
-                let params = ParakeetInferenceParams::default();
-                engine.transcribe_samples(samples, Some(params))
-            }
+impl From<TaskChoice> for TranscriptionTask {
+    fn from(choice: TaskChoice) -> Self {
+        match choice {
+            TaskChoice::Transcribe => TranscriptionTask::Transcribe,
+            TaskChoice::Translate => TranscriptionTask::Translate,
         }
     }
 }
@@ -88,14 +49,22 @@ impl RealtimeTranscriber for EngineWrapper {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    let mut engine = args.engine.create_engine();
+    let mut engine = args.engine.create();
+    if !engine.supports_task(args.task.into()) {
+        return Err(format!("the {} engine does not support translation", args.engine).into());
+    }
     engine.load_model(&args.model_path)?;
 
     send_message(&OutboundMessage::Ready {
         engine: format!("{:?}", args.engine),
     })?;
 
-    let mut session = RealtimeSession::new(engine, args.language.clone());
+    let options = TranscribeOptions {
+        task: args.task.into(),
+        ..Default::default()
+    };
+    let transcriber = ConfiguredTranscriber::new(engine, options);
+    let mut session = RealtimeSession::new(transcriber, args.language.clone());
     let stdin = io::stdin();
 
     for line in stdin.lock().lines() {
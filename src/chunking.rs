@@ -0,0 +1,344 @@
+//! Chunked transcription of long recordings.
+//!
+//! Running a whole recording through an engine in one call needs memory
+//! proportional to its length, which is impractical for hour-long files.
+//! This module splits 16 kHz mono samples into overlapping chunks, preferring
+//! to cut in the quietest part of the audio near each chunk boundary, runs an
+//! engine on every chunk and stitches the results back together.
+//!
+//! Stitching shifts segment timestamps by each chunk's start time and resolves
+//! the overlap between neighbouring chunks with a midpoint rule: a segment is
+//! kept from the earlier chunk if its midpoint lies before the middle of the
+//! overlap, and from the later chunk otherwise, so words spoken in the
+//! overlap appear exactly once. This relies on the engine reporting
+//! segments; see [`stitch_results`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use transcribe_rs::{audio, chunking::{transcribe_chunked, ChunkingParams}};
+//! use transcribe_rs::{TranscriptionEngine, engines::whisper::WhisperEngine};
+//! use std::path::Path;
+//!
+//! let mut engine = WhisperEngine::new();
+//! engine.load_model(Path::new("models/whisper-medium-q4_1.bin"))?;
+//!
+//! let samples = audio::read_audio_samples(Path::new("town-hall.wav"))?;
+//! let result = transcribe_chunked(&mut engine, &samples, None, &ChunkingParams::default())?;
+//! println!("Transcription: {}", result.text);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::audio::{AudioSource, TARGET_SAMPLE_RATE};
+use crate::{
+    TranscribeError, TranscriptionEngine, TranscriptionResult, TranscriptionSegment,
+    SOURCE_WINDOW_SECONDS,
+};
+
+/// Length, in samples, of the frames compared when searching for silence (25 ms).
+const SILENCE_FRAME_SIZE: usize = 400;
+
+/// Parameters controlling how audio is split into chunks.
+#[derive(Debug, Clone)]
+pub struct ChunkingParams {
+    /// Maximum length of a chunk in seconds. Defaults to
+    /// [`SOURCE_WINDOW_SECONDS`].
+    pub chunk_seconds: f32,
+    /// Audio shared between consecutive chunks in seconds. Clamped to half of
+    /// `chunk_seconds`.
+    pub overlap_seconds: f32,
+    /// How far before the nominal end of a chunk to look for the quietest
+    /// point to cut at, in seconds. `0.0` always cuts at the nominal end.
+    pub silence_search_seconds: f32,
+}
+
+impl Default for ChunkingParams {
+    fn default() -> Self {
+        Self {
+            chunk_seconds: SOURCE_WINDOW_SECONDS,
+            overlap_seconds: 2.0,
+            silence_search_seconds: 5.0,
+        }
+    }
+}
+
+/// A chunk of the input, as a half-open range of sample indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioChunk {
+    /// Index of the first sample in the chunk
+    pub start: usize,
+    /// Index one past the last sample in the chunk
+    pub end: usize,
+}
+
+impl AudioChunk {
+    /// Start time of the chunk in seconds.
+    pub fn start_seconds(&self) -> f32 {
+        self.start as f32 / TARGET_SAMPLE_RATE as f32
+    }
+
+    /// End time of the chunk in seconds.
+    pub fn end_seconds(&self) -> f32 {
+        self.end as f32 / TARGET_SAMPLE_RATE as f32
+    }
+}
+
+/// Split 16 kHz mono `samples` into overlapping chunks.
+///
+/// Each chunk is at most `chunk_seconds` long. Unless it reaches the end of
+/// the input, it ends at the quietest 25 ms frame within the last
+/// `silence_search_seconds` of the chunk, and the next chunk starts
+/// `overlap_seconds` before that cut.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::chunking::{plan_chunks, ChunkingParams};
+///
+/// let samples = vec![0.1; 16_000 * 75];
+/// let chunks = plan_chunks(&samples, &ChunkingParams::default());
+/// assert_eq!(chunks.len(), 3);
+/// assert_eq!(chunks.last().unwrap().end, samples.len());
+/// ```
+pub fn plan_chunks(samples: &[f32], params: &ChunkingParams) -> Vec<AudioChunk> {
+    let rate = TARGET_SAMPLE_RATE as f32;
+    let chunk_len = chunk_samples(params);
+    let overlap = ((params.overlap_seconds.max(0.0) * rate) as usize).min(chunk_len / 2);
+    let search = ((params.silence_search_seconds.max(0.0) * rate) as usize)
+        .min(chunk_len - overlap - SILENCE_FRAME_SIZE / 2);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < samples.len() {
+        let nominal_end = start + chunk_len;
+        if nominal_end >= samples.len() {
+            chunks.push(AudioChunk {
+                start,
+                end: samples.len(),
+            });
+            break;
+        }
+
+        let end = quietest_point(samples, nominal_end - search, nominal_end);
+        chunks.push(AudioChunk { start, end });
+        start = end - overlap;
+    }
+
+    chunks
+}
+
+/// Maximum length of a chunk in samples.
+fn chunk_samples(params: &ChunkingParams) -> usize {
+    ((params.chunk_seconds * TARGET_SAMPLE_RATE as f32) as usize).max(SILENCE_FRAME_SIZE)
+}
+
+/// Find the centre of the lowest-energy frame whose centre lies in
+/// `from..=to`, preferring later frames on ties.
+fn quietest_point(samples: &[f32], from: usize, to: usize) -> usize {
+    let half = SILENCE_FRAME_SIZE / 2;
+
+    (from.max(half)..=to)
+        .rev()
+        .step_by(half)
+        .map(|centre| {
+            let frame = &samples[centre - half..(centre + half).min(samples.len())];
+            let energy = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+            (centre, energy)
+        })
+        .fold((to, f32::INFINITY), |best, candidate| {
+            if candidate.1 < best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Combine per-chunk results into one result for the whole input.
+///
+/// `results[i]` must hold the transcription of `chunks[i]`, with timestamps
+/// relative to the start of that chunk. Segments are shifted to absolute
+/// time and duplicates from the overlap between chunks are dropped using the
+/// midpoint rule described in the [module documentation](self).
+///
+/// The combined text is rebuilt from the kept segments, with a space
+/// inserted between two segments unless one of them already provides the
+/// whitespace. The language is taken from the first chunk that reports one.
+///
+/// The overlap can only be resolved through segment timestamps. A chunk with
+/// text but no segments contributes its text unchanged if it does not
+/// overlap its neighbours, and fails with
+/// [`TranscribeError::UnstitchableChunk`] if it does, since its overlapping
+/// words would otherwise appear twice.
+pub fn stitch_results(
+    chunks: &[AudioChunk],
+    results: Vec<TranscriptionResult>,
+) -> Result<TranscriptionResult, TranscribeError> {
+    let mut text = String::new();
+    let mut segments: Vec<TranscriptionSegment> = Vec::new();
+    let mut language = None;
+
+    for (i, (chunk, result)) in chunks.iter().zip(results).enumerate() {
+        language = language.or(result.language);
+        if result.segments.is_empty() {
+            if result.text.trim().is_empty() {
+                continue;
+            }
+            let overlaps_prev = i
+                .checked_sub(1)
+                .is_some_and(|prev| chunks[prev].end > chunk.start);
+            let overlaps_next = chunks.get(i + 1).is_some_and(|next| chunk.end > next.start);
+            if overlaps_prev || overlaps_next {
+                return Err(TranscribeError::UnstitchableChunk(i));
+            }
+            push_text(&mut text, result.text.trim());
+            continue;
+        }
+
+        let keep_from = match i.checked_sub(1).map(|prev| &chunks[prev]) {
+            Some(prev) => (chunk.start_seconds() + prev.end_seconds()) / 2.0,
+            None => f32::NEG_INFINITY,
+        };
+        let keep_until = match chunks.get(i + 1) {
+            Some(next) => (next.start_seconds() + chunk.end_seconds()) / 2.0,
+            None => f32::INFINITY,
+        };
+
+        for segment in result.segments {
+            let segment = segment.offset_by(chunk.start_seconds());
+            let midpoint = (segment.start + segment.end) / 2.0;
+            if midpoint < keep_from || midpoint >= keep_until {
+                continue;
+            }
+            push_text(&mut text, &segment.text);
+            segments.push(segment);
+        }
+    }
+
+    Ok(TranscriptionResult {
+        text: text.trim().to_string(),
+        segments,
+        language,
+    })
+}
+
+/// Append `piece` to `text`, separated by a space unless either side already
+/// has whitespace at the join.
+fn push_text(text: &mut String, piece: &str) {
+    if !text.ends_with(char::is_whitespace) && !piece.starts_with(char::is_whitespace) {
+        text.push(' ');
+    }
+    text.push_str(piece);
+}
+
+/// Transcribe `samples` chunk by chunk with a caller-supplied function.
+///
+/// `transcribe` receives the samples of each chunk in order and returns its
+/// result with chunk-relative timestamps. This is the building block for
+/// [`transcribe_chunked`] and can be used with engines that do not implement
+/// [`TranscriptionEngine`] directly, such as a boxed
+/// [`DynTranscriber`](crate::DynTranscriber).
+pub fn transcribe_chunked_with<F>(
+    samples: &[f32],
+    params: &ChunkingParams,
+    mut transcribe: F,
+) -> Result<TranscriptionResult, TranscribeError>
+where
+    F: FnMut(Vec<f32>) -> Result<TranscriptionResult, TranscribeError>,
+{
+    let chunks = plan_chunks(samples, params);
+    let results = chunks
+        .iter()
+        .map(|chunk| transcribe(samples[chunk.start..chunk.end].to_vec()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    stitch_results(&chunks, results)
+}
+
+/// Transcribe audio pulled from `source` chunk by chunk with a
+/// caller-supplied function.
+///
+/// The chunks are the ones [`plan_chunks`] would choose for the whole input,
+/// but each is transcribed as soon as enough audio has arrived to place its
+/// cut, so only about one chunk of audio is buffered at a time. The results
+/// are combined with [`stitch_results`].
+pub fn transcribe_source_chunked_with<F>(
+    source: &mut dyn AudioSource,
+    params: &ChunkingParams,
+    mut transcribe: F,
+) -> Result<TranscriptionResult, TranscribeError>
+where
+    F: FnMut(Vec<f32>) -> Result<TranscriptionResult, TranscribeError>,
+{
+    // With this much audio buffered, the first chunk's cut no longer depends
+    // on audio that is still to come
+    let lookahead = chunk_samples(params) + SILENCE_FRAME_SIZE / 2;
+
+    let mut buffer: Vec<f32> = Vec::new();
+    let mut buffer_start = 0;
+    let mut chunks = Vec::new();
+    let mut results = Vec::new();
+
+    loop {
+        let frame = source.next_frame()?;
+        let exhausted = frame.is_none();
+        if let Some(frame) = frame {
+            buffer.extend(frame);
+        }
+
+        while buffer.len() > lookahead {
+            let planned = plan_chunks(&buffer, params);
+            let (chunk, next_start) = (planned[0], planned[1].start);
+            results.push(transcribe(buffer[chunk.start..chunk.end].to_vec())?);
+            chunks.push(AudioChunk {
+                start: buffer_start + chunk.start,
+                end: buffer_start + chunk.end,
+            });
+            buffer.drain(..next_start);
+            buffer_start += next_start;
+        }
+
+        if exhausted {
+            for chunk in plan_chunks(&buffer, params) {
+                results.push(transcribe(buffer[chunk.start..chunk.end].to_vec())?);
+                chunks.push(AudioChunk {
+                    start: buffer_start + chunk.start,
+                    end: buffer_start + chunk.end,
+                });
+            }
+            break;
+        }
+    }
+
+    stitch_results(&chunks, results)
+}
+
+/// Transcribe `samples` with `engine`, one overlapping chunk at a time.
+///
+/// # Arguments
+///
+/// * `engine` - A loaded transcription engine
+/// * `samples` - Audio samples as f32 values (16kHz, mono)
+/// * `params` - Optional engine-specific inference parameters, applied to every chunk
+/// * `chunking` - How to split the audio into chunks
+///
+/// # Returns
+///
+/// Returns one transcription result for the whole input, with timestamps
+/// relative to the start of `samples`.
+pub fn transcribe_chunked<E>(
+    engine: &mut E,
+    samples: &[f32],
+    params: Option<E::InferenceParams>,
+    chunking: &ChunkingParams,
+) -> Result<TranscriptionResult, TranscribeError>
+where
+    E: TranscriptionEngine + ?Sized,
+    E::InferenceParams: Clone,
+{
+    transcribe_chunked_with(samples, chunking, |chunk| {
+        engine.transcribe_samples(chunk, params.clone())
+    })
+}
@@ -46,7 +46,116 @@
 //! }
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! # Choosing an Engine at Runtime
+//!
+//! [`create_engine`] builds a boxed [`DynTranscriber`] from an engine name,
+//! which avoids matching on engine types in application code:
+//!
+//! ```rust,no_run
+//! use transcribe_rs::{TranscribeOptions, engines::create_engine};
+//! use std::path::Path;
+//!
+//! let mut engine = create_engine("whisper", Path::new("models/whisper-medium-q4_1.bin"))?;
+//! let result = engine.transcribe_file(Path::new("audio.wav"), &TranscribeOptions::default())?;
+//! # let _ = result;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
 #[cfg(feature = "parakeet")]
 pub mod parakeet;
+pub mod validation;
 pub mod whisper;
+
+pub use validation::{ModelProblem, ModelValidation};
+
+use crate::{DynTranscriber, TranscribeError};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The transcription engines available in this build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineKind {
+    /// [`whisper::WhisperEngine`]
+    Whisper,
+    /// [`parakeet::ParakeetEngine`]
+    #[cfg(feature = "parakeet")]
+    Parakeet,
+}
+
+impl EngineKind {
+    /// Every engine compiled into this build.
+    pub const ALL: &'static [EngineKind] = &[
+        EngineKind::Whisper,
+        #[cfg(feature = "parakeet")]
+        EngineKind::Parakeet,
+    ];
+
+    /// The lowercase name accepted by [`EngineKind::from_str`].
+    pub fn name(self) -> &'static str {
+        match self {
+            EngineKind::Whisper => "whisper",
+            #[cfg(feature = "parakeet")]
+            EngineKind::Parakeet => "parakeet",
+        }
+    }
+
+    /// Create an unloaded engine of this kind.
+    pub fn create(self) -> Box<dyn DynTranscriber> {
+        match self {
+            EngineKind::Whisper => Box::new(whisper::WhisperEngine::new()),
+            #[cfg(feature = "parakeet")]
+            EngineKind::Parakeet => Box::new(parakeet::ParakeetEngine::new()),
+        }
+    }
+
+    /// Check the model at `model_path` for this engine without loading it.
+    pub fn validate_model(self, model_path: &Path) -> ModelValidation {
+        match self {
+            EngineKind::Whisper => whisper::validate_model(model_path),
+            #[cfg(feature = "parakeet")]
+            EngineKind::Parakeet => parakeet::validate_model(model_path),
+        }
+    }
+}
+
+impl fmt::Display for EngineKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for EngineKind {
+    type Err = TranscribeError;
+
+    /// Parse an engine name, ignoring ASCII case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        EngineKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| TranscribeError::UnknownEngine(name.to_string()))
+    }
+}
+
+/// Create an engine by name and load the model at `model_path` with default
+/// model parameters.
+///
+/// # Arguments
+///
+/// * `name` - Engine name, e.g. `"whisper"` or `"parakeet"` (case-insensitive)
+/// * `model_path` - Path to the model file or directory
+///
+/// # Returns
+///
+/// Returns the loaded engine, [`TranscribeError::UnknownEngine`] if no engine
+/// with that name is compiled in, or the error raised while loading the model.
+pub fn create_engine(
+    name: &str,
+    model_path: &Path,
+) -> Result<Box<dyn DynTranscriber>, TranscribeError> {
+    let mut engine = name.parse::<EngineKind>()?.create();
+    engine.load_model(model_path)?;
+    Ok(engine)
+}
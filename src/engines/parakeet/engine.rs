@@ -54,6 +54,28 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
+//! ## Tuning ONNX Runtime
+//!
+//! ```rust,no_run
+//! use transcribe_rs::{TranscriptionEngine, engines::parakeet::{ParakeetEngine, ParakeetModelParams, SessionOptions}};
+//! use std::path::PathBuf;
+//!
+//! let mut engine = ParakeetEngine::new();
+//! engine.load_model_with_params(
+//!     &PathBuf::from("models/parakeet-v0.3"),
+//!     ParakeetModelParams {
+//!         session: SessionOptions {
+//!             intra_threads: Some(2),
+//!             inter_threads: Some(1),
+//!             optimized_model_dir: Some(PathBuf::from("cache/parakeet-v0.3")),
+//!             ..Default::default()
+//!         },
+//!         ..ParakeetModelParams::int8()
+//!     },
+//! )?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
 //! ## With Custom Timestamp Granularity
 //!
 //! ```rust,no_run
@@ -65,6 +87,7 @@
 //!
 //! let params = ParakeetInferenceParams {
 //!     timestamp_granularity: TimestampGranularity::Word,  // Get word-level timestamps
+//!     ..Default::default()
 //! };
 //!
 //! let result = engine.transcribe_file(&PathBuf::from("audio.wav"), Some(params))?;
@@ -76,25 +99,25 @@
 //! ```
 
 use crate::{
-    engines::parakeet::{model::ParakeetModel, timestamps::convert_timestamps},
-    TranscriptionEngine, TranscriptionResult,
+    engines::{
+        parakeet::{
+            model::{
+                DecodingStrategy, ParakeetModel, ParakeetModelBytes, SessionOptions,
+                SessionProviders, DEFAULT_TDT_DURATIONS,
+            },
+            timestamps::convert_timestamps,
+        },
+        ModelValidation,
+    },
+    CancellationToken, ProgressObserver, TranscribeError, TranscribeOptions, TranscriptionEngine,
+    TranscriptionResult,
 };
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-/// Granularity level for timestamp generation.
-///
-/// Controls the level of detail in the timing information returned
-/// by the Parakeet engine.
-#[derive(Debug, Clone, Default, PartialEq)]
-pub enum TimestampGranularity {
-    /// Token-level timestamps (most detailed, default)
-    #[default]
-    Token,
-    /// Word-level timestamps (grouped tokens into words)
-    Word,
-    /// Segment-level timestamps (larger phrases/sentences)
-    Segment,
-}
+pub use crate::TimestampGranularity;
 
 /// Quantization type for Parakeet model loading.
 ///
@@ -111,11 +134,31 @@ pub enum QuantizationType {
 
 /// Parameters for configuring Parakeet model loading.
 ///
-/// Controls model quantization settings for balancing performance vs accuracy.
-#[derive(Debug, Clone, Default)]
+/// Controls model quantization settings for balancing performance vs accuracy,
+/// and how ONNX Runtime runs the model.
+#[derive(Debug, Clone)]
 pub struct ParakeetModelParams {
     /// The quantization type to use for the model
     pub quantization: QuantizationType,
+
+    /// ONNX Runtime threading, optimization and memory settings
+    pub session: SessionOptions,
+
+    /// Frames covered by each duration the TDT joint network predicts, in
+    /// the order of its duration logits. Parakeet TDT models use
+    /// [`DEFAULT_TDT_DURATIONS`]; models without duration outputs ignore
+    /// this. Must not be empty.
+    pub tdt_durations: Vec<usize>,
+}
+
+impl Default for ParakeetModelParams {
+    fn default() -> Self {
+        Self {
+            quantization: QuantizationType::default(),
+            session: SessionOptions::default(),
+            tdt_durations: DEFAULT_TDT_DURATIONS.to_vec(),
+        }
+    }
 }
 
 impl ParakeetModelParams {
@@ -133,6 +176,7 @@ impl ParakeetModelParams {
     pub fn fp32() -> Self {
         Self {
             quantization: QuantizationType::FP32,
+            ..Default::default()
         }
     }
 
@@ -150,6 +194,7 @@ impl ParakeetModelParams {
     pub fn int8() -> Self {
         Self {
             quantization: QuantizationType::Int8,
+            ..Default::default()
         }
     }
 
@@ -167,7 +212,10 @@ impl ParakeetModelParams {
     /// let params = ParakeetModelParams::quantized(QuantizationType::Int8);
     /// ```
     pub fn quantized(quantization: QuantizationType) -> Self {
-        Self { quantization }
+        Self {
+            quantization,
+            ..Default::default()
+        }
     }
 }
 
@@ -179,14 +227,138 @@ impl ParakeetModelParams {
 pub struct ParakeetInferenceParams {
     /// The granularity level for timestamp generation
     pub timestamp_granularity: TimestampGranularity,
+
+    /// How the transducer output is searched. Beam searches can be more
+    /// accurate on noisy audio at several times the decoding cost.
+    pub decoding: DecodingStrategy,
+
+    /// Observer notified of decoding progress. Segments are reported once
+    /// decoding has finished, since Parakeet decodes the whole input in one pass.
+    pub progress: Option<Arc<dyn ProgressObserver>>,
+
+    /// Token that stops the run with [`TranscribeError::Cancelled`] when cancelled.
+    /// It is checked before each model stage and on every decoded frame.
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl Default for ParakeetInferenceParams {
     fn default() -> Self {
         Self {
             timestamp_granularity: TimestampGranularity::Token,
+            decoding: DecodingStrategy::Greedy,
+            progress: None,
+            cancellation: None,
+        }
+    }
+}
+
+/// Parakeet detects the spoken language itself and configures threading when
+/// the model is loaded, so the language and thread count are not used.
+/// Parakeet only transcribes, so [`DynTranscriber`](crate::DynTranscriber)
+/// rejects other tasks before these parameters are built.
+impl From<&TranscribeOptions> for ParakeetInferenceParams {
+    fn from(options: &TranscribeOptions) -> Self {
+        Self {
+            timestamp_granularity: options
+                .timestamp_granularity
+                .clone()
+                .unwrap_or(TimestampGranularity::Token),
+            decoding: DecodingStrategy::Greedy,
+            progress: options.progress.clone(),
+            cancellation: options.cancellation.clone(),
+        }
+    }
+}
+
+/// Check that `model_dir` contains every file a Parakeet model needs,
+/// without loading it.
+///
+/// The encoder and decoder/joint graphs may be present in full precision,
+/// Int8, or both. A directory with only Int8 graphs is reported as valid but
+/// must be loaded with [`ParakeetModelParams::int8`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use transcribe_rs::engines::parakeet;
+/// use std::path::Path;
+///
+/// let report = parakeet::validate_model(Path::new("models/parakeet-v0.3"));
+/// for problem in &report.problems {
+///     eprintln!("{problem}");
+/// }
+/// ```
+pub fn validate_model(model_dir: &Path) -> ModelValidation {
+    check_model_dir(model_dir, false)
+}
+
+/// Validate `model_dir`, optionally requiring the full precision graphs.
+fn check_model_dir(model_dir: &Path, full_precision: bool) -> ModelValidation {
+    let mut report = ModelValidation::new(model_dir);
+    if !model_dir.is_dir() {
+        report.missing(model_dir.to_path_buf());
+        return report;
+    }
+
+    for name in ["encoder-model", "decoder_joint-model"] {
+        let regular = model_dir.join(format!("{name}.onnx"));
+        let quantized = model_dir.join(format!("{name}.int8.onnx"));
+        if !regular.is_file() && (full_precision || !quantized.is_file()) {
+            report.missing(regular);
+        }
+    }
+
+    let preprocessor = model_dir.join("nemo128.onnx");
+    if !preprocessor.is_file() {
+        report.missing(preprocessor);
+    }
+
+    let vocab = model_dir.join("vocab.txt");
+    match fs::read_to_string(&vocab) {
+        Ok(content) => {
+            if ParakeetModel::parse_vocab(&content).is_err() {
+                report.invalid(vocab, "missing <blk> token");
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => report.missing(vocab),
+        Err(err) => report.unreadable(vocab, err),
+    }
+
+    report
+}
+
+/// Reject model parameters that cannot describe a usable model.
+fn check_model_params(params: &ParakeetModelParams) -> Result<(), TranscribeError> {
+    if params.tdt_durations.is_empty() {
+        return Err(TranscribeError::InvalidParameter(
+            "tdt_durations must not be empty".to_string(),
+        ));
+    }
+    check_session_options(&params.session)
+}
+
+/// Reject session options ONNX Runtime would silently reinterpret.
+fn check_session_options(options: &SessionOptions) -> Result<(), TranscribeError> {
+    // ONNX Runtime reads a thread count of zero as "choose for me"
+    for (name, value) in [
+        ("intra_threads", options.intra_threads),
+        ("inter_threads", options.inter_threads),
+    ] {
+        if value == Some(0) {
+            return Err(TranscribeError::InvalidParameter(format!(
+                "{name} must be at least 1"
+            )));
+        }
+    }
+    if let Some(dir) = &options.optimized_model_dir {
+        if dir.exists() && !dir.is_dir() {
+            return Err(TranscribeError::InvalidParameter(format!(
+                "optimized_model_dir {} is not a directory",
+                dir.display()
+            )));
         }
     }
+    Ok(())
 }
 
 /// Parakeet speech recognition engine.
@@ -239,6 +411,55 @@ impl ParakeetEngine {
             model: None,
         }
     }
+
+    /// The execution providers registered for each session of the loaded
+    /// model, or `None` if no model is loaded. Registration does not show
+    /// which operators each provider ends up running.
+    pub fn execution_providers(&self) -> Option<&[SessionProviders]> {
+        self.model.as_ref().map(ParakeetModel::execution_providers)
+    }
+
+    /// Load a model from ONNX graphs and vocabulary held in memory.
+    ///
+    /// Useful when the model is shipped inside an encrypted or compressed
+    /// bundle and should never be written to disk. ONNX Runtime copies the
+    /// graphs, so the buffers can be dropped once this returns.
+    /// `params.quantization` and `params.session.optimized_model_dir` are
+    /// ignored; pass the graphs you want to run.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use transcribe_rs::engines::parakeet::{ParakeetEngine, ParakeetModelBytes, ParakeetModelParams};
+    ///
+    /// let encoder = std::fs::read("models/parakeet-v0.3/encoder-model.int8.onnx")?;
+    /// let decoder_joint = std::fs::read("models/parakeet-v0.3/decoder_joint-model.int8.onnx")?;
+    /// let preprocessor = std::fs::read("models/parakeet-v0.3/nemo128.onnx")?;
+    /// let vocab = std::fs::read_to_string("models/parakeet-v0.3/vocab.txt")?;
+    ///
+    /// let mut engine = ParakeetEngine::new();
+    /// engine.load_model_from_bytes(
+    ///     ParakeetModelBytes {
+    ///         encoder: &encoder,
+    ///         decoder_joint: &decoder_joint,
+    ///         preprocessor: &preprocessor,
+    ///         vocab: &vocab,
+    ///     },
+    ///     ParakeetModelParams::default(),
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_model_from_bytes(
+        &mut self,
+        model: ParakeetModelBytes<'_>,
+        params: ParakeetModelParams,
+    ) -> Result<(), TranscribeError> {
+        check_model_params(&params)?;
+        let model = ParakeetModel::from_bytes(model, &params.session)?;
+        self.model = Some(model.with_tdt_durations(params.tdt_durations));
+        self.loaded_model_path = None;
+        Ok(())
+    }
 }
 
 impl Drop for ParakeetEngine {
@@ -255,12 +476,23 @@ impl TranscriptionEngine for ParakeetEngine {
         &mut self,
         model_path: &Path,
         params: Self::ModelParams,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), TranscribeError> {
+        check_model_params(&params)?;
+        if !model_path.is_dir() {
+            return Err(TranscribeError::ModelFileMissing(model_path.to_path_buf()));
+        }
+
         let quantized = match params.quantization {
             QuantizationType::FP32 => false,
             QuantizationType::Int8 => true,
         };
-        let model = ParakeetModel::new(model_path, quantized)?;
+        let report = check_model_dir(model_path, !quantized);
+        if !report.is_valid() {
+            return Err(TranscribeError::InvalidModel(report));
+        }
+
+        let model = ParakeetModel::with_options(model_path, quantized, &params.session)?
+            .with_tdt_durations(params.tdt_durations);
 
         self.model = Some(model);
         self.loaded_model_path = Some(model_path.to_path_buf());
@@ -276,24 +508,45 @@ impl TranscriptionEngine for ParakeetEngine {
         &mut self,
         samples: Vec<f32>,
         params: Option<Self::InferenceParams>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
-        let model: &mut ParakeetModel = self
-            .model
-            .as_mut()
-            .ok_or("Model not loaded. Call load_model() first.")?;
-
+    ) -> Result<TranscriptionResult, TranscribeError> {
         let parakeet_params = params.unwrap_or_default();
+        parakeet_params.decoding.validate()?;
+        if parakeet_params
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(TranscribeError::Cancelled);
+        }
+
+        let model: &mut ParakeetModel =
+            self.model.as_mut().ok_or(TranscribeError::ModelNotLoaded)?;
+
+        let observer = parakeet_params.progress.as_deref();
 
         // Get the timestamped result from the model
-        let timestamped_result = model.transcribe_samples(samples)?;
+        let timestamped_result = model.transcribe_samples_with_control(
+            samples,
+            parakeet_params.decoding,
+            observer,
+            parakeet_params.cancellation.as_ref(),
+        )?;
 
         // Convert timestamps based on requested granularity
         let segments =
             convert_timestamps(&timestamped_result, parakeet_params.timestamp_granularity);
 
+        if let Some(observer) = observer {
+            for segment in &segments {
+                observer.on_segment(segment);
+            }
+            observer.on_progress(100.0);
+        }
+
         Ok(TranscriptionResult {
             text: timestamped_result.text,
             segments,
+            language: None,
         })
     }
 }
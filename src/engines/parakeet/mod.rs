@@ -60,6 +60,7 @@
 //! // Configure for word-level timestamps
 //! let params = ParakeetInferenceParams {
 //!     timestamp_granularity: TimestampGranularity::Word,
+//!     ..Default::default()
 //! };
 //!
 //! let result = engine.transcribe_file(&PathBuf::from("audio.wav"), Some(params))?;
@@ -75,8 +76,11 @@ pub mod model;
 pub mod timestamps;
 
 pub use engine::{
-    ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, QuantizationType,
+    validate_model, ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, QuantizationType,
     TimestampGranularity,
 };
-pub use model::{ParakeetError, ParakeetModel, TimestampedResult};
+pub use model::{
+    DecodingStrategy, ExecutionProvider, OptimizationLevel, ParakeetError, ParakeetModel,
+    ParakeetModelBytes, SessionOptions, SessionProviders, TimestampedResult, DEFAULT_TDT_DURATIONS,
+};
 pub use timestamps::{convert_timestamps, WordBoundary};
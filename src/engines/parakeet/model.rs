@@ -1,14 +1,27 @@
 use ndarray::{Array, Array1, Array2, Array3, ArrayD, ArrayViewD, IxDyn};
 use once_cell::sync::Lazy;
-use ort::execution_providers::CPUExecutionProvider;
+#[cfg(feature = "cuda")]
+use ort::execution_providers::CUDAExecutionProvider;
+#[cfg(feature = "coreml")]
+use ort::execution_providers::CoreMLExecutionProvider;
+#[cfg(feature = "directml")]
+use ort::execution_providers::DirectMLExecutionProvider;
+#[cfg(feature = "openvino")]
+use ort::execution_providers::OpenVINOExecutionProvider;
+#[cfg(feature = "xnnpack")]
+use ort::execution_providers::XNNPACKExecutionProvider;
+use ort::execution_providers::{CPUExecutionProvider, ExecutionProvider as OrtExecutionProvider};
 use ort::inputs;
-use ort::session::builder::GraphOptimizationLevel;
+use ort::session::builder::{GraphOptimizationLevel, SessionBuilder};
 use ort::session::Session;
 use ort::value::TensorRef;
 use regex::Regex;
 
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use crate::{CancellationToken, ProgressObserver, TranscribeError};
 
 pub type DecoderState = (Array3<f32>, Array3<f32>);
 
@@ -16,14 +29,42 @@ const SUBSAMPLING_FACTOR: usize = 8;
 const WINDOW_SIZE: f32 = 0.01;
 const MAX_TOKENS_PER_STEP: usize = 10;
 
+/// Frame durations predicted by Parakeet TDT models, in the order of the
+/// joint network's duration logits.
+pub const DEFAULT_TDT_DURATIONS: [usize; 5] = [0, 1, 2, 3, 4];
+
 static DECODE_SPACE_RE: Lazy<Result<Regex, regex::Error>> =
     Lazy::new(|| Regex::new(r"\A\s|\s\B|(\s)\b"));
 
 #[derive(Debug, Clone)]
 pub struct TimestampedResult {
     pub text: String,
+    /// Start time of each token in seconds
     pub timestamps: Vec<f32>,
+    /// End time of each token in seconds, from the durations predicted by
+    /// TDT models. Empty for models without duration outputs.
+    pub end_timestamps: Vec<f32>,
     pub tokens: Vec<String>,
+    /// Softmax probability the model gave each token, among all vocabulary
+    /// entries at the frame where it was emitted
+    pub probabilities: Vec<f32>,
+    /// Entropy in nats of the vocabulary distribution each token was chosen
+    /// from. Higher values mean the model was less certain.
+    pub entropies: Vec<f32>,
+}
+
+/// Output of decoding one utterance, in encoder frames.
+#[derive(Clone, Default)]
+struct DecodedSequence {
+    tokens: Vec<i32>,
+    /// Frame at which each token was emitted
+    frames: Vec<usize>,
+    /// Frame after the last one covered by each token (TDT models only)
+    end_frames: Vec<usize>,
+    /// Softmax probability of each token
+    probabilities: Vec<f32>,
+    /// Entropy of the distribution each token was chosen from
+    entropies: Vec<f32>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -40,6 +81,372 @@ pub enum ParakeetError {
     OutputNotFound(String),
     #[error("Failed to get tensor shape for input: {0}")]
     TensorShape(String),
+    #[error("Decoding cancelled")]
+    Cancelled,
+    #[error("Model predicts {found} durations but {expected} are configured")]
+    DurationCount { expected: usize, found: usize },
+}
+
+/// The files of a Parakeet model directory, held in memory.
+///
+/// Pass the quantized or full-precision graphs as desired; unlike loading
+/// from a directory, no variant is selected automatically.
+#[derive(Debug, Clone, Copy)]
+pub struct ParakeetModelBytes<'a> {
+    /// Contents of `encoder-model.onnx` or `encoder-model.int8.onnx`
+    pub encoder: &'a [u8],
+    /// Contents of `decoder_joint-model.onnx` or `decoder_joint-model.int8.onnx`
+    pub decoder_joint: &'a [u8],
+    /// Contents of `nemo128.onnx`
+    pub preprocessor: &'a [u8],
+    /// Contents of `vocab.txt`
+    pub vocab: &'a str,
+}
+
+/// Graph optimizations applied by ONNX Runtime when creating a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// No graph optimizations
+    Disable,
+    /// Semantics-preserving rewrites such as constant folding and redundant
+    /// node elimination
+    Level1,
+    /// Level 1 plus operator fusions
+    Level2,
+    /// All optimizations, including hardware-specific layout changes (default)
+    #[default]
+    Level3,
+}
+
+impl OptimizationLevel {
+    fn to_ort(self) -> GraphOptimizationLevel {
+        match self {
+            OptimizationLevel::Disable => GraphOptimizationLevel::Disable,
+            OptimizationLevel::Level1 => GraphOptimizationLevel::Level1,
+            OptimizationLevel::Level2 => GraphOptimizationLevel::Level2,
+            OptimizationLevel::Level3 => GraphOptimizationLevel::Level3,
+        }
+    }
+}
+
+/// ONNX Runtime execution providers a Parakeet model can run on.
+///
+/// Providers other than `Cpu` are only registered when the matching crate
+/// feature (`cuda`, `coreml`, `directml`, `openvino` or `xnnpack`) is
+/// enabled and ONNX Runtime was built with support for them; otherwise they
+/// are skipped with the reason recorded in [`SessionProviders::skipped`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionProvider {
+    /// The default CPU provider, always available
+    Cpu,
+    /// XNNPACK, optimized CPU kernels for Arm and x86
+    Xnnpack,
+    /// Intel OpenVINO
+    OpenVino,
+    /// NVIDIA CUDA
+    Cuda,
+    /// Apple CoreML
+    CoreMl,
+    /// DirectML on Windows
+    DirectMl,
+}
+
+impl ExecutionProvider {
+    fn register(
+        self,
+        options: &SessionOptions,
+        builder: &mut SessionBuilder,
+    ) -> Result<(), String> {
+        match self {
+            ExecutionProvider::Cpu => register_with(
+                CPUExecutionProvider::default().with_arena_allocator(options.memory_arena),
+                builder,
+            ),
+            #[cfg(feature = "xnnpack")]
+            ExecutionProvider::Xnnpack => {
+                register_with(XNNPACKExecutionProvider::default(), builder)
+            }
+            #[cfg(not(feature = "xnnpack"))]
+            ExecutionProvider::Xnnpack => Err(missing_feature("xnnpack")),
+            #[cfg(feature = "openvino")]
+            ExecutionProvider::OpenVino => {
+                register_with(OpenVINOExecutionProvider::default(), builder)
+            }
+            #[cfg(not(feature = "openvino"))]
+            ExecutionProvider::OpenVino => Err(missing_feature("openvino")),
+            #[cfg(feature = "cuda")]
+            ExecutionProvider::Cuda => register_with(CUDAExecutionProvider::default(), builder),
+            #[cfg(not(feature = "cuda"))]
+            ExecutionProvider::Cuda => Err(missing_feature("cuda")),
+            #[cfg(feature = "coreml")]
+            ExecutionProvider::CoreMl => register_with(CoreMLExecutionProvider::default(), builder),
+            #[cfg(not(feature = "coreml"))]
+            ExecutionProvider::CoreMl => Err(missing_feature("coreml")),
+            #[cfg(feature = "directml")]
+            ExecutionProvider::DirectMl => {
+                register_with(DirectMLExecutionProvider::default(), builder)
+            }
+            #[cfg(not(feature = "directml"))]
+            ExecutionProvider::DirectMl => Err(missing_feature("directml")),
+        }
+    }
+}
+
+/// Register `provider` on `builder`, describing any failure.
+fn register_with(
+    provider: impl OrtExecutionProvider,
+    builder: &mut SessionBuilder,
+) -> Result<(), String> {
+    provider.register(builder).map_err(|err| err.to_string())
+}
+
+/// Reason a provider is skipped when its crate feature is disabled.
+#[cfg(not(all(
+    feature = "cuda",
+    feature = "coreml",
+    feature = "directml",
+    feature = "openvino",
+    feature = "xnnpack"
+)))]
+fn missing_feature(feature: &str) -> String {
+    format!("transcribe-rs was built without the `{feature}` feature")
+}
+
+/// The execution providers registered for one session of a loaded model.
+///
+/// This records which providers ONNX Runtime accepted for the session, not
+/// where its nodes run: a registered provider may still leave operators it
+/// does not support to a later provider, usually `Cpu`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionProviders {
+    /// Name of the model graph, e.g. `"encoder-model"`
+    pub session: String,
+    /// Providers that registered successfully, in priority order. ONNX
+    /// Runtime runs each operator on the first of these that supports it.
+    pub registered: Vec<ExecutionProvider>,
+    /// Providers that failed to register, with the reason
+    pub skipped: Vec<(ExecutionProvider, String)>,
+}
+
+impl SessionProviders {
+    /// The highest priority provider that registered. ONNX Runtime assigns
+    /// it every operator it supports, but how much of the graph that covers
+    /// is not reported.
+    pub fn primary(&self) -> ExecutionProvider {
+        self.registered
+            .first()
+            .copied()
+            .unwrap_or(ExecutionProvider::Cpu)
+    }
+}
+
+/// ONNX Runtime settings applied to every session of a Parakeet model
+/// (encoder, decoder/joint and preprocessor).
+#[derive(Debug, Clone)]
+pub struct SessionOptions {
+    /// Threads used within a single operator. `None` lets ONNX Runtime
+    /// choose (one per physical core); `Some(0)` is rejected.
+    pub intra_threads: Option<usize>,
+
+    /// Threads used to run independent operators concurrently when
+    /// `parallel_execution` is enabled. `None` lets ONNX Runtime choose;
+    /// `Some(0)` is rejected.
+    pub inter_threads: Option<usize>,
+
+    /// Whether independent operators may run concurrently.
+    pub parallel_execution: bool,
+
+    /// Graph optimizations applied when a session is created.
+    pub optimization_level: OptimizationLevel,
+
+    /// Whether the CPU allocator uses a memory arena. Disabling it lowers
+    /// peak memory use at some cost in speed.
+    pub memory_arena: bool,
+
+    /// Whether ONNX Runtime pre-plans allocations from the shapes seen in
+    /// earlier runs. Mostly useful when input lengths are similar.
+    pub memory_pattern: bool,
+
+    /// Execution providers to try, in priority order. Providers that fail
+    /// to register are skipped, and `Cpu` is always added last as the final
+    /// fallback. See [`ParakeetModel::execution_providers`] for the outcome.
+    pub execution_providers: Vec<ExecutionProvider>,
+
+    /// Directory in which optimized graphs are cached. The first load writes
+    /// `<model>-<key>.optimized.onnx` files here and later loads read them
+    /// instead of optimizing again. The key covers the source graph's path,
+    /// size and modification time, the optimization level and the registered
+    /// execution providers, so several models and configurations can share
+    /// one directory. Must not be an existing file. Not used for models
+    /// loaded from memory.
+    pub optimized_model_dir: Option<PathBuf>,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        Self {
+            intra_threads: None,
+            inter_threads: None,
+            parallel_execution: true,
+            optimization_level: OptimizationLevel::Level3,
+            memory_arena: true,
+            memory_pattern: true,
+            execution_providers: vec![ExecutionProvider::Cpu],
+            optimized_model_dir: None,
+        }
+    }
+}
+
+/// How the transducer output is searched for the most likely transcript.
+///
+/// Beam searches run the decoder once per hypothesis and expansion, so they
+/// cost several times as much as greedy decoding; the encoder cost is the
+/// same for every strategy.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DecodingStrategy {
+    /// Emit the most likely token at each step (default)
+    #[default]
+    Greedy,
+    /// Keep the `beam_size` most likely hypotheses, expanding each with its
+    /// `beam_size` most likely tokens
+    BeamSearch {
+        /// Number of hypotheses kept
+        beam_size: usize,
+    },
+    /// Modified adaptive expansion search: like `BeamSearch`, but each
+    /// hypothesis is only expanded with tokens close to its best one, which
+    /// prunes most expansions on confident frames
+    Maes {
+        /// Number of hypotheses kept
+        beam_size: usize,
+        /// Extra candidate tokens considered per hypothesis beyond `beam_size`
+        expansion_beta: usize,
+        /// Candidates whose log-probability is more than this below the best
+        /// token's are discarded
+        expansion_gamma: f32,
+    },
+}
+
+impl DecodingStrategy {
+    /// Beam search keeping `beam_size` hypotheses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::parakeet::DecodingStrategy;
+    ///
+    /// let strategy = DecodingStrategy::beam_search(4);
+    /// ```
+    pub fn beam_search(beam_size: usize) -> Self {
+        Self::BeamSearch { beam_size }
+    }
+
+    /// Modified adaptive expansion search keeping `beam_size` hypotheses,
+    /// with NeMo's default expansion settings (`beta` 2, `gamma` 2.3).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::parakeet::DecodingStrategy;
+    ///
+    /// let strategy = DecodingStrategy::maes(4);
+    /// ```
+    pub fn maes(beam_size: usize) -> Self {
+        Self::Maes {
+            beam_size,
+            expansion_beta: 2,
+            expansion_gamma: 2.3,
+        }
+    }
+
+    /// Reject strategies that would keep no hypotheses: a zero `beam_size`,
+    /// or an `expansion_gamma` that is negative or not finite, which would
+    /// discard even the best token.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::parakeet::DecodingStrategy;
+    ///
+    /// assert!(DecodingStrategy::maes(4).validate().is_ok());
+    /// assert!(DecodingStrategy::beam_search(0).validate().is_err());
+    /// ```
+    pub fn validate(self) -> Result<(), TranscribeError> {
+        match self {
+            DecodingStrategy::Greedy => Ok(()),
+            DecodingStrategy::BeamSearch { beam_size }
+            | DecodingStrategy::Maes { beam_size, .. }
+                if beam_size == 0 =>
+            {
+                Err(TranscribeError::InvalidParameter(
+                    "beam_size must be at least 1".to_string(),
+                ))
+            }
+            DecodingStrategy::Maes {
+                expansion_gamma, ..
+            } if !expansion_gamma.is_finite() || expansion_gamma < 0.0 => {
+                Err(TranscribeError::InvalidParameter(format!(
+                    "expansion_gamma must be finite and non-negative, got {expansion_gamma}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Candidate tokens for one hypothesis given its token log-probabilities,
+    /// most likely first.
+    fn candidates(self, log_probs: &[f32]) -> Vec<usize> {
+        let (count, gamma) = match self {
+            DecodingStrategy::Greedy => (1, None),
+            DecodingStrategy::BeamSearch { beam_size } => (beam_size, None),
+            DecodingStrategy::Maes {
+                beam_size,
+                expansion_beta,
+                expansion_gamma,
+            } => (beam_size + expansion_beta, Some(expansion_gamma)),
+        };
+
+        let mut order: Vec<usize> = (0..log_probs.len()).collect();
+        order.sort_by(|&a, &b| {
+            log_probs[b]
+                .partial_cmp(&log_probs[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        order.truncate(count);
+        if let (Some(gamma), Some(&best)) = (gamma, order.first()) {
+            let threshold = log_probs[best] - gamma;
+            order.retain(|&idx| log_probs[idx] >= threshold);
+        }
+        order
+    }
+
+    fn beam_size(self) -> usize {
+        match self {
+            DecodingStrategy::Greedy => 1,
+            DecodingStrategy::BeamSearch { beam_size }
+            | DecodingStrategy::Maes { beam_size, .. } => beam_size,
+        }
+    }
+}
+
+/// A partial transcript tracked by beam search.
+#[derive(Clone)]
+struct Hypothesis {
+    decoded: DecodedSequence,
+    /// Total log-probability of the tokens, blanks and durations so far
+    score: f32,
+    /// Decoder state before consuming the last token
+    state: DecoderState,
+    /// Next encoder frame to decode
+    frame: usize,
+}
+
+impl Hypothesis {
+    /// Score used to pick the final transcript. Normalizing by length keeps
+    /// short transcripts from winning just because they have fewer terms.
+    fn normalized_score(&self) -> f32 {
+        self.score / (self.decoded.tokens.len() + 1) as f32
+    }
 }
 
 pub struct ParakeetModel {
@@ -49,6 +456,9 @@ pub struct ParakeetModel {
     vocab: Vec<String>,
     blank_idx: i32,
     vocab_size: usize,
+    /// Frames covered by each TDT duration logit
+    durations: Vec<usize>,
+    providers: Vec<SessionProviders>,
 }
 
 impl Drop for ParakeetModel {
@@ -62,11 +472,76 @@ impl Drop for ParakeetModel {
 
 impl ParakeetModel {
     pub fn new<P: AsRef<Path>>(model_dir: P, quantized: bool) -> Result<Self, ParakeetError> {
-        let encoder = Self::init_session(&model_dir, "encoder-model", None, quantized)?;
-        let decoder_joint = Self::init_session(&model_dir, "decoder_joint-model", None, quantized)?;
-        let preprocessor = Self::init_session(&model_dir, "nemo128", None, false)?;
+        Self::with_options(model_dir, quantized, &SessionOptions::default())
+    }
+
+    /// Load a model directory with custom ONNX Runtime session options.
+    pub fn with_options<P: AsRef<Path>>(
+        model_dir: P,
+        quantized: bool,
+        options: &SessionOptions,
+    ) -> Result<Self, ParakeetError> {
+        let (encoder, encoder_providers) =
+            Self::init_session(&model_dir, "encoder-model", options, quantized)?;
+        let (decoder_joint, decoder_joint_providers) =
+            Self::init_session(&model_dir, "decoder_joint-model", options, quantized)?;
+        let (preprocessor, preprocessor_providers) =
+            Self::init_session(&model_dir, "nemo128", options, false)?;
 
         let (vocab, blank_idx) = Self::load_vocab(&model_dir)?;
+
+        Ok(Self::from_parts(
+            encoder,
+            decoder_joint,
+            preprocessor,
+            vocab,
+            blank_idx,
+            vec![
+                encoder_providers,
+                decoder_joint_providers,
+                preprocessor_providers,
+            ],
+        ))
+    }
+
+    /// Create a model from ONNX graphs and vocabulary held in memory.
+    pub fn from_bytes(
+        model: ParakeetModelBytes<'_>,
+        options: &SessionOptions,
+    ) -> Result<Self, ParakeetError> {
+        // Parse the vocabulary first; it is cheap and fails without creating
+        // any session.
+        let (vocab, blank_idx) = Self::parse_vocab(model.vocab)?;
+
+        let (encoder, encoder_providers) =
+            Self::init_session_from_memory("encoder-model", model.encoder, options)?;
+        let (decoder_joint, decoder_joint_providers) =
+            Self::init_session_from_memory("decoder_joint-model", model.decoder_joint, options)?;
+        let (preprocessor, preprocessor_providers) =
+            Self::init_session_from_memory("nemo128", model.preprocessor, options)?;
+
+        Ok(Self::from_parts(
+            encoder,
+            decoder_joint,
+            preprocessor,
+            vocab,
+            blank_idx,
+            vec![
+                encoder_providers,
+                decoder_joint_providers,
+                preprocessor_providers,
+            ],
+        ))
+    }
+
+    fn from_parts(
+        encoder: Session,
+        decoder_joint: Session,
+        preprocessor: Session,
+        vocab: Vec<String>,
+        blank_idx: i32,
+        providers: Vec<SessionProviders>,
+    ) -> Self {
         let vocab_size = vocab.len();
 
         log::info!(
@@ -75,24 +550,47 @@ impl ParakeetModel {
             blank_idx
         );
 
-        Ok(Self {
+        Self {
             encoder,
             decoder_joint,
             preprocessor,
             vocab,
             blank_idx,
             vocab_size,
-        })
+            durations: DEFAULT_TDT_DURATIONS.to_vec(),
+            providers,
+        }
+    }
+
+    /// Use `durations` as the frames covered by each of the model's TDT
+    /// duration logits, in order, instead of [`DEFAULT_TDT_DURATIONS`].
+    ///
+    /// Decoding fails with [`ParakeetError::DurationCount`] if the model
+    /// predicts a different number of durations. Models without duration
+    /// outputs ignore the list.
+    pub fn with_tdt_durations(mut self, durations: Vec<usize>) -> Self {
+        self.durations = durations;
+        self
+    }
+
+    /// The frames covered by each TDT duration logit.
+    pub fn tdt_durations(&self) -> &[usize] {
+        &self.durations
+    }
+
+    /// The execution providers registered for each session (encoder,
+    /// decoder/joint and preprocessor). See [`SessionProviders`] for what
+    /// registration does and does not tell you.
+    pub fn execution_providers(&self) -> &[SessionProviders] {
+        &self.providers
     }
 
     fn init_session<P: AsRef<Path>>(
         model_dir: P,
         model_name: &str,
-        intra_threads: Option<usize>,
+        options: &SessionOptions,
         try_quantized: bool,
-    ) -> Result<Session, ParakeetError> {
-        let providers = vec![CPUExecutionProvider::default().build()];
-
+    ) -> Result<(Session, SessionProviders), ParakeetError> {
         // Try quantized version first if requested, fallback to regular version
         let model_filename = if try_quantized {
             let quantized_name = format!("{}.int8.onnx", model_name);
@@ -114,35 +612,135 @@ impl ParakeetModel {
             regular_name
         };
 
+        let (mut builder, providers) = Self::session_builder(model_name, options)?;
+        let mut model_path = model_dir.as_ref().join(&model_filename);
+
+        // On a cache miss ONNX Runtime writes the optimized graph while the
+        // session is created. It goes to a temporary file that is renamed
+        // into place afterwards, so an interrupted write is never loaded.
+        let mut pending_cache = None;
+        if let Some(cache_dir) = &options.optimized_model_dir {
+            let cached_path =
+                optimized_cache_path(cache_dir, &model_path, options, &providers.registered)?;
+            if cached_path.is_file() {
+                log::info!("Loading optimized model from {}...", cached_path.display());
+                model_path = cached_path;
+            } else {
+                fs::create_dir_all(cache_dir)?;
+                let temp_path = cached_path.with_extension(format!("tmp-{}", std::process::id()));
+                builder = builder.with_optimized_model_path(&temp_path)?;
+                pending_cache = Some((temp_path, cached_path));
+            }
+        }
+
+        let session = match builder.commit_from_file(model_path) {
+            Ok(session) => session,
+            Err(err) => {
+                if let Some((temp_path, _)) = &pending_cache {
+                    let _ = fs::remove_file(temp_path);
+                }
+                return Err(err.into());
+            }
+        };
+        if let Some((temp_path, cached_path)) = pending_cache {
+            if let Err(err) = fs::rename(&temp_path, &cached_path) {
+                log::warn!(
+                    "Failed to cache optimized model at {}: {}",
+                    cached_path.display(),
+                    err
+                );
+                let _ = fs::remove_file(&temp_path);
+            }
+        }
+        Self::log_inputs(&model_filename, &session);
+
+        Ok((session, providers))
+    }
+
+    fn init_session_from_memory(
+        model_name: &str,
+        model_bytes: &[u8],
+        options: &SessionOptions,
+    ) -> Result<(Session, SessionProviders), ParakeetError> {
+        log::info!(
+            "Loading model {} from memory ({} bytes)...",
+            model_name,
+            model_bytes.len()
+        );
+
+        let (builder, providers) = Self::session_builder(model_name, options)?;
+        let session = builder.commit_from_memory(model_bytes)?;
+        Self::log_inputs(model_name, &session);
+
+        Ok((session, providers))
+    }
+
+    fn session_builder(
+        model_name: &str,
+        options: &SessionOptions,
+    ) -> Result<(SessionBuilder, SessionProviders), ParakeetError> {
         let mut builder = Session::builder()?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_execution_providers(providers)?
-            .with_parallel_execution(true)?;
-
-        if let Some(threads) = intra_threads {
-            builder = builder
-                .with_intra_threads(threads)?
-                .with_inter_threads(threads)?;
+            .with_optimization_level(options.optimization_level.to_ort())?
+            .with_parallel_execution(options.parallel_execution)?
+            .with_memory_pattern(options.memory_pattern)?;
+
+        let mut providers = SessionProviders {
+            session: model_name.to_string(),
+            registered: Vec::new(),
+            skipped: Vec::new(),
+        };
+        let fallback = (!options
+            .execution_providers
+            .contains(&ExecutionProvider::Cpu))
+        .then_some(ExecutionProvider::Cpu);
+        for provider in options.execution_providers.iter().copied().chain(fallback) {
+            match provider.register(options, &mut builder) {
+                Ok(()) => providers.registered.push(provider),
+                Err(err) => {
+                    log::warn!(
+                        "Execution provider {:?} unavailable for '{}': {}",
+                        provider,
+                        model_name,
+                        err
+                    );
+                    providers.skipped.push((provider, err));
+                }
+            }
         }
+        log::info!(
+            "Model '{}' registered {:?}",
+            model_name,
+            providers.registered
+        );
 
-        let session = builder.commit_from_file(model_dir.as_ref().join(&model_filename))?;
+        if let Some(threads) = options.intra_threads {
+            builder = builder.with_intra_threads(threads)?;
+        }
+        if let Some(threads) = options.inter_threads {
+            builder = builder.with_inter_threads(threads)?;
+        }
 
+        Ok((builder, providers))
+    }
+
+    fn log_inputs(model_name: &str, session: &Session) {
         for input in &session.inputs {
             log::info!(
                 "Model '{}' input: name={}, type={:?}",
-                model_filename,
+                model_name,
                 input.name,
                 input.input_type
             );
         }
-
-        Ok(session)
     }
 
     fn load_vocab<P: AsRef<Path>>(model_dir: P) -> Result<(Vec<String>, i32), ParakeetError> {
         let vocab_path = model_dir.as_ref().join("vocab.txt");
         let content = fs::read_to_string(vocab_path)?;
+        Self::parse_vocab(&content)
+    }
 
+    pub(crate) fn parse_vocab(content: &str) -> Result<(Vec<String>, i32), ParakeetError> {
         let mut max_id = 0;
         let mut tokens_with_ids: Vec<(String, usize)> = Vec::new();
         let mut blank_idx: Option<usize> = None;
@@ -326,91 +924,278 @@ impl ParakeetModel {
         waveforms: &ArrayViewD<f32>,
         waveforms_len: &ArrayViewD<i64>,
     ) -> Result<Vec<TimestampedResult>, ParakeetError> {
+        self.recognize_batch_with_control(
+            waveforms,
+            waveforms_len,
+            DecodingStrategy::Greedy,
+            None,
+            None,
+        )
+    }
+
+    /// Like [`recognize_batch`](Self::recognize_batch), decoding with
+    /// `strategy`, reporting decoding progress to `progress` and stopping
+    /// with [`ParakeetError::Cancelled`] once `cancellation` is cancelled.
+    pub fn recognize_batch_with_control(
+        &mut self,
+        waveforms: &ArrayViewD<f32>,
+        waveforms_len: &ArrayViewD<i64>,
+        strategy: DecodingStrategy,
+        progress: Option<&dyn ProgressObserver>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<Vec<TimestampedResult>, ParakeetError> {
+        let check_cancelled = || match cancellation {
+            Some(token) if token.is_cancelled() => Err(ParakeetError::Cancelled),
+            _ => Ok(()),
+        };
+
         // Preprocess and encode
+        check_cancelled()?;
         let (features, features_lens) = self.preprocess(waveforms, waveforms_len)?;
+        check_cancelled()?;
         let (encoder_out, encoder_out_lens) =
             self.encode(&features.view(), &features_lens.view())?;
 
         // Decode for each batch item
         let mut results = Vec::new();
         for (encodings, &encodings_len) in encoder_out.outer_iter().zip(encoder_out_lens.iter()) {
-            let (tokens, timestamps) =
-                self.decode_sequence(&encodings.view(), encodings_len as usize)?;
-            let result = self.decode_tokens(tokens, timestamps);
+            let decoded = match strategy {
+                DecodingStrategy::Greedy => self.decode_sequence(
+                    &encodings.view(),
+                    encodings_len as usize,
+                    progress,
+                    cancellation,
+                )?,
+                _ => self.beam_decode_sequence(
+                    &encodings.view(),
+                    encodings_len as usize,
+                    strategy,
+                    progress,
+                    cancellation,
+                )?,
+            };
+            let result = self.decode_tokens(decoded);
             results.push(result);
         }
 
         Ok(results)
     }
 
+    /// Greedy transducer decoding.
+    ///
+    /// For TDT models the joint network also predicts how many frames the
+    /// emitted token (or blank) covers. Decoding jumps ahead by that many
+    /// frames, which skips most of the blank frames, and the duration gives
+    /// each token's end time. Plain RNN-T models advance one frame per blank.
     fn decode_sequence(
         &mut self,
         encodings: &ArrayViewD<f32>, // [time_steps, 1024]
         encodings_len: usize,
-    ) -> Result<(Vec<i32>, Vec<usize>), ParakeetError> {
+        progress: Option<&dyn ProgressObserver>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<DecodedSequence, ParakeetError> {
         let mut prev_state = self.create_decoder_state()?;
-        let mut tokens = Vec::new();
-        let mut timestamps = Vec::new();
+        let mut decoded = DecodedSequence::default();
 
         let mut t = 0;
         let mut emitted_tokens = 0;
+        let mut reported_percent = 0;
 
         while t < encodings_len {
+            check_progress(
+                t,
+                encodings_len,
+                &mut reported_percent,
+                progress,
+                cancellation,
+            )?;
+
             let encoder_step = encodings.slice(ndarray::s![t, ..]);
             // Convert to dynamic dimension to match decode_step parameter type
             let encoder_step_dyn = encoder_step.to_owned().into_dyn();
             let (probs, new_state) =
-                self.decode_step(&tokens, &prev_state, &encoder_step_dyn.view())?;
-
-            // For TDT models, split output into vocab logits and duration logits
-            // output[:vocab_size] = vocabulary logits
-            // output[vocab_size:] = duration logits
-            let vocab_logits_slice = probs.as_slice().ok_or_else(|| {
-                ParakeetError::Shape(ndarray::ShapeError::from_kind(
-                    ndarray::ErrorKind::IncompatibleShape,
-                ))
-            })?;
-
-            let vocab_logits = if probs.len() > self.vocab_size {
-                // TDT model - extract only vocabulary logits
-                log::trace!(
-                    "TDT model detected: splitting {} logits into vocab({}) + duration",
-                    probs.len(),
-                    self.vocab_size
-                );
-                &vocab_logits_slice[..self.vocab_size]
-            } else {
-                // Regular RNN-T model
-                vocab_logits_slice
-            };
+                self.decode_step(&decoded.tokens, &prev_state, &encoder_step_dyn.view())?;
+            let (vocab_logits, duration_logits) = self.split_logits(&probs)?;
 
             // Get argmax token from vocabulary logits only
-            let token = vocab_logits
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-                .map(|(idx, _)| idx as i32)
-                .unwrap_or(self.blank_idx);
+            let token_log_probs = log_softmax(vocab_logits);
+            let token = argmax(&token_log_probs).map_or(self.blank_idx, |idx| idx as i32);
+
+            // Plain RNN-T models have no duration logits
+            let step = argmax(duration_logits).map(|idx| self.durations[idx]);
 
             if token != self.blank_idx {
                 prev_state = new_state;
-                tokens.push(token);
-                timestamps.push(t);
+                decoded.tokens.push(token);
+                decoded.frames.push(t);
+                decoded
+                    .probabilities
+                    .push(token_log_probs[token as usize].exp());
+                decoded.entropies.push(entropy(&token_log_probs));
+                if let Some(step) = step {
+                    decoded
+                        .end_frames
+                        .push((t + step.max(1)).min(encodings_len));
+                }
                 emitted_tokens += 1;
             }
 
-            // Step logic from Python - simplified since step is always -1
-            if token == self.blank_idx || emitted_tokens == MAX_TOKENS_PER_STEP {
-                t += 1;
-                emitted_tokens = 0;
+            match step {
+                Some(step) if step > 0 => {
+                    t += step;
+                    emitted_tokens = 0;
+                }
+                _ if token == self.blank_idx || emitted_tokens == MAX_TOKENS_PER_STEP => {
+                    t += 1;
+                    emitted_tokens = 0;
+                }
+                _ => {}
             }
         }
 
-        Ok((tokens, timestamps))
+        Ok(decoded)
     }
 
-    fn decode_tokens(&self, ids: Vec<i32>, timestamps: Vec<usize>) -> TimestampedResult {
-        let tokens: Vec<String> = ids
+    /// Transducer beam search for [`DecodingStrategy::BeamSearch`] and
+    /// [`DecodingStrategy::Maes`].
+    ///
+    /// Every hypothesis waiting at frame `t` is extended with its candidate
+    /// tokens, repeatedly up to `MAX_TOKENS_PER_STEP` tokens per frame, while
+    /// candidates that move past the frame (blanks, and tokens with a non-zero
+    /// TDT duration) wait for their next frame. After each round only the best
+    /// `beam_size` hypotheses of each frame are kept, and hypotheses with the
+    /// same tokens at the same frame are merged. Hypotheses are only ranked
+    /// against others at the same frame, since one that jumped ahead on a
+    /// long TDT duration has already paid for the frames it skipped. Each
+    /// expansion takes the most likely duration, as greedy decoding does.
+    fn beam_decode_sequence(
+        &mut self,
+        encodings: &ArrayViewD<f32>, // [time_steps, 1024]
+        encodings_len: usize,
+        strategy: DecodingStrategy,
+        progress: Option<&dyn ProgressObserver>,
+        cancellation: Option<&CancellationToken>,
+    ) -> Result<DecodedSequence, ParakeetError> {
+        let beam_size = strategy.beam_size();
+        let mut beam = vec![Hypothesis {
+            decoded: DecodedSequence::default(),
+            score: 0.0,
+            state: self.create_decoder_state()?,
+            frame: 0,
+        }];
+        let mut reported_percent = 0;
+        // Whether the joint network predicts durations, known after the first step
+        let mut tdt = false;
+
+        for t in 0..encodings_len {
+            let (mut active, mut waiting): (Vec<_>, Vec<_>) =
+                beam.into_iter().partition(|hyp| hyp.frame == t);
+            if active.is_empty() {
+                beam = waiting;
+                continue;
+            }
+            check_progress(
+                t,
+                encodings_len,
+                &mut reported_percent,
+                progress,
+                cancellation,
+            )?;
+
+            let encoder_step = encodings.slice(ndarray::s![t, ..]).to_owned().into_dyn();
+
+            for _ in 0..MAX_TOKENS_PER_STEP {
+                let mut expanded = Vec::new();
+                for hyp in &active {
+                    let (probs, new_state) =
+                        self.decode_step(&hyp.decoded.tokens, &hyp.state, &encoder_step.view())?;
+                    let (vocab_logits, duration_logits) = self.split_logits(&probs)?;
+                    let token_log_probs = log_softmax(vocab_logits);
+                    let duration_log_probs = log_softmax(duration_logits);
+                    let duration_idx = argmax(duration_logits);
+                    let duration = duration_idx.map(|idx| self.durations[idx]);
+                    let duration_score = duration_idx.map_or(0.0, |idx| duration_log_probs[idx]);
+                    let token_entropy = entropy(&token_log_probs);
+                    tdt |= duration.is_some();
+
+                    for token in strategy.candidates(&token_log_probs) {
+                        let mut next = hyp.clone();
+                        next.score += token_log_probs[token] + duration_score;
+
+                        if token as i32 == self.blank_idx {
+                            next.frame = t + duration.unwrap_or(1).max(1);
+                            waiting.push(next);
+                            continue;
+                        }
+
+                        next.state = new_state.clone();
+                        next.frame = t + duration.unwrap_or(0);
+                        next.decoded.tokens.push(token as i32);
+                        next.decoded.frames.push(t);
+                        next.decoded
+                            .probabilities
+                            .push(token_log_probs[token].exp());
+                        next.decoded.entropies.push(token_entropy);
+                        if let Some(duration) = duration {
+                            next.decoded
+                                .end_frames
+                                .push((t + duration.max(1)).min(encodings_len));
+                        }
+                        if next.frame > t {
+                            waiting.push(next);
+                        } else {
+                            expanded.push(next);
+                        }
+                    }
+                }
+
+                active = prune(expanded, beam_size);
+                waiting = prune_per_frame(waiting, beam_size);
+
+                let durations = if tdt { &self.durations[..] } else { &[] };
+                if beam_is_settled(&active, &waiting, &next_frames(t, durations), beam_size) {
+                    active.clear();
+                    break;
+                }
+            }
+
+            // Hypotheses still emitting after MAX_TOKENS_PER_STEP move on, as
+            // in greedy decoding
+            for mut hyp in active {
+                hyp.frame = t + 1;
+                waiting.push(hyp);
+            }
+            beam = prune_per_frame(waiting, beam_size);
+        }
+
+        Ok(beam
+            .into_iter()
+            .max_by(|a, b| {
+                a.normalized_score()
+                    .partial_cmp(&b.normalized_score())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|hyp| hyp.decoded)
+            .unwrap_or_default())
+    }
+
+    /// Split the joint network output into vocabulary logits and, for TDT
+    /// models, duration logits (`output[vocab_size..]`).
+    fn split_logits<'a>(
+        &self,
+        probs: &'a ArrayD<f32>,
+    ) -> Result<(&'a [f32], &'a [f32]), ParakeetError> {
+        let logits = probs.as_slice().ok_or_else(|| {
+            ParakeetError::Shape(ndarray::ShapeError::from_kind(
+                ndarray::ErrorKind::IncompatibleShape,
+            ))
+        })?;
+        split_joint_logits(logits, self.vocab_size, &self.durations)
+    }
+
+    fn decode_tokens(&self, decoded: DecodedSequence) -> TimestampedResult {
+        let tokens: Vec<String> = decoded
+            .tokens
             .iter()
             .filter_map(|&id| {
                 let idx = id as usize;
@@ -435,21 +1220,40 @@ impl ParakeetModel {
             Err(_) => tokens.join(""), // Fallback if regex failed to compile
         };
 
-        let float_timestamps: Vec<f32> = timestamps
-            .iter()
-            .map(|&t| WINDOW_SIZE * SUBSAMPLING_FACTOR as f32 * t as f32)
-            .collect();
+        let frame_seconds = |frames: &[usize]| -> Vec<f32> {
+            frames
+                .iter()
+                .map(|&t| WINDOW_SIZE * SUBSAMPLING_FACTOR as f32 * t as f32)
+                .collect()
+        };
 
         TimestampedResult {
             text,
-            timestamps: float_timestamps,
+            timestamps: frame_seconds(&decoded.frames),
+            end_timestamps: frame_seconds(&decoded.end_frames),
             tokens,
+            probabilities: decoded.probabilities,
+            entropies: decoded.entropies,
         }
     }
 
     pub fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
+    ) -> Result<TimestampedResult, ParakeetError> {
+        self.transcribe_samples_with_control(samples, DecodingStrategy::Greedy, None, None)
+    }
+
+    /// Like [`transcribe_samples`](Self::transcribe_samples), decoding with
+    /// `strategy`, reporting decoding progress to `progress` and stopping
+    /// with [`ParakeetError::Cancelled`] once `cancellation` is cancelled.
+    /// `strategy` must pass [`DecodingStrategy::validate`].
+    pub fn transcribe_samples_with_control(
+        &mut self,
+        samples: Vec<f32>,
+        strategy: DecodingStrategy,
+        progress: Option<&dyn ProgressObserver>,
+        cancellation: Option<&CancellationToken>,
     ) -> Result<TimestampedResult, ParakeetError> {
         let batch_size = 1;
         let samples_len = samples.len();
@@ -461,7 +1265,13 @@ impl ParakeetModel {
         let waveforms_lens = Array1::from_vec(vec![samples_len as i64]).into_dyn();
 
         // Run recognition to get detailed results
-        let results = self.recognize_batch(&waveforms.view(), &waveforms_lens.view())?;
+        let results = self.recognize_batch_with_control(
+            &waveforms.view(),
+            &waveforms_lens.view(),
+            strategy,
+            progress,
+            cancellation,
+        )?;
 
         // Extract the first (and only) result
         let timestamped_result = results.into_iter().next().ok_or_else(|| {
@@ -474,3 +1284,396 @@ impl ParakeetModel {
         Ok(timestamped_result)
     }
 }
+
+/// Split joint network logits into vocabulary logits and duration logits,
+/// checking that a TDT model predicts one logit per configured duration.
+fn split_joint_logits<'a>(
+    logits: &'a [f32],
+    vocab_size: usize,
+    durations: &[usize],
+) -> Result<(&'a [f32], &'a [f32]), ParakeetError> {
+    let (vocab_logits, duration_logits) = logits.split_at(vocab_size.min(logits.len()));
+    if !duration_logits.is_empty() && duration_logits.len() != durations.len() {
+        return Err(ParakeetError::DurationCount {
+            expected: durations.len(),
+            found: duration_logits.len(),
+        });
+    }
+    Ok((vocab_logits, duration_logits))
+}
+
+/// Index of the largest value, or `None` if `values` is empty.
+fn argmax(values: &[f32]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(idx, _)| idx)
+}
+
+/// Natural-log softmax of `logits`.
+fn log_softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let log_sum = logits.iter().map(|&x| (x - max).exp()).sum::<f32>().ln() + max;
+    logits.iter().map(|&x| x - log_sum).collect()
+}
+
+/// Entropy in nats of the distribution given by `log_probs`.
+fn entropy(log_probs: &[f32]) -> f32 {
+    -log_probs
+        .iter()
+        .filter(|lp| lp.is_finite())
+        .map(|&lp| lp.exp() * lp)
+        .sum::<f32>()
+}
+
+/// `ln(exp(a) + exp(b))` without overflow.
+fn log_add(a: f32, b: f32) -> f32 {
+    let max = a.max(b);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+/// Merge hypotheses with the same tokens at the same frame, then keep the
+/// `beam_size` best, highest score first.
+fn prune(mut hyps: Vec<Hypothesis>, beam_size: usize) -> Vec<Hypothesis> {
+    let by_score = |a: &Hypothesis, b: &Hypothesis| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    hyps.sort_by(by_score);
+
+    let mut kept: Vec<Hypothesis> = Vec::with_capacity(beam_size);
+    for hyp in hyps {
+        if let Some(same) = kept
+            .iter_mut()
+            .find(|kept| kept.frame == hyp.frame && kept.decoded.tokens == hyp.decoded.tokens)
+        {
+            same.score = log_add(same.score, hyp.score);
+        } else if kept.len() < beam_size {
+            kept.push(hyp);
+        }
+    }
+    kept.sort_by(by_score);
+    kept
+}
+
+/// [`prune`] the hypotheses of each frame separately, so hypotheses are
+/// only ranked against others that have decoded as far. The result is
+/// ordered by frame, then by score within each frame.
+fn prune_per_frame(hyps: Vec<Hypothesis>, beam_size: usize) -> Vec<Hypothesis> {
+    let mut by_frame: BTreeMap<usize, Vec<Hypothesis>> = BTreeMap::new();
+    for hyp in hyps {
+        by_frame.entry(hyp.frame).or_default().push(hyp);
+    }
+    by_frame
+        .into_values()
+        .flat_map(|group| prune(group, beam_size))
+        .collect()
+}
+
+/// Frames a hypothesis expanding on frame `t` can move on to: the next
+/// frame, and for TDT models every frame reachable with one of `durations`.
+fn next_frames(t: usize, durations: &[usize]) -> Vec<usize> {
+    let mut frames: Vec<usize> = durations.iter().map(|&d| t + d.max(1)).collect();
+    frames.push(t + 1);
+    frames.sort_unstable();
+    frames.dedup();
+    frames
+}
+
+/// Path of the cached optimized graph for `model_path` in `cache_dir`.
+///
+/// The file name carries a hash of everything the optimized graph depends
+/// on: the source graph (path, size and modification time), the optimization
+/// level and the execution providers it was built for.
+fn optimized_cache_path(
+    cache_dir: &Path,
+    model_path: &Path,
+    options: &SessionOptions,
+    providers: &[ExecutionProvider],
+) -> Result<PathBuf, ParakeetError> {
+    let metadata = fs::metadata(model_path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .unwrap_or_default();
+    let source = fs::canonicalize(model_path).unwrap_or_else(|_| model_path.to_path_buf());
+
+    // FNV-1a, which unlike std's hasher is stable across Rust releases
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let key = format!(
+        "{}\0{}\0{}\0{:?}\0{:?}",
+        source.display(),
+        metadata.len(),
+        modified.as_nanos(),
+        options.optimization_level,
+        providers
+    );
+    for byte in key.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    let stem = model_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(cache_dir.join(format!("{stem}-{hash:016x}.optimized.onnx")))
+}
+
+/// Whether expanding `active` further on this frame is pointless: every frame
+/// in `next_frames` already holds `beam_size` waiting hypotheses that score
+/// better than the best active one, and expansions only lower scores.
+///
+/// `active` must be sorted by score and `waiting` come from
+/// [`prune_per_frame`].
+fn beam_is_settled(
+    active: &[Hypothesis],
+    waiting: &[Hypothesis],
+    next_frames: &[usize],
+    beam_size: usize,
+) -> bool {
+    let Some(best_active) = active.first() else {
+        return true;
+    };
+    next_frames.iter().all(|&frame| {
+        waiting
+            .iter()
+            .filter(|hyp| hyp.frame == frame)
+            .nth(beam_size - 1)
+            .is_some_and(|worst_kept| best_active.score < worst_kept.score)
+    })
+}
+
+/// Return [`ParakeetError::Cancelled`] once `cancellation` is cancelled, and
+/// report progress at frame `t` of `len` in whole-percent steps, so observers
+/// are not called per frame.
+fn check_progress(
+    t: usize,
+    len: usize,
+    reported_percent: &mut usize,
+    progress: Option<&dyn ProgressObserver>,
+    cancellation: Option<&CancellationToken>,
+) -> Result<(), ParakeetError> {
+    if cancellation.is_some_and(CancellationToken::is_cancelled) {
+        return Err(ParakeetError::Cancelled);
+    }
+    if let Some(observer) = progress {
+        let percent = t * 100 / len;
+        if percent > *reported_percent {
+            *reported_percent = percent;
+            observer.on_progress(percent as f32);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimized_cache_path_depends_on_model_and_configuration() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        let v2 = dir.path().join("v2");
+        let v3 = dir.path().join("v3");
+        for model_dir in [&v2, &v3] {
+            fs::create_dir(model_dir).unwrap();
+            fs::write(model_dir.join("encoder-model.onnx"), b"graph").unwrap();
+        }
+        let options = SessionOptions::default();
+        let cpu = [ExecutionProvider::Cpu];
+        let path = |model: &Path, options: &SessionOptions, providers: &[ExecutionProvider]| {
+            optimized_cache_path(
+                &cache,
+                &model.join("encoder-model.onnx"),
+                options,
+                providers,
+            )
+            .unwrap()
+        };
+
+        let base = path(&v2, &options, &cpu);
+        assert_eq!(base.parent(), Some(cache.as_path()));
+        assert!(base
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("encoder-model-"));
+        assert_eq!(path(&v2, &options, &cpu), base);
+
+        assert_ne!(path(&v3, &options, &cpu), base);
+        let level1 = SessionOptions {
+            optimization_level: OptimizationLevel::Level1,
+            ..Default::default()
+        };
+        assert_ne!(path(&v2, &level1, &cpu), base);
+        assert_ne!(
+            path(
+                &v2,
+                &options,
+                &[ExecutionProvider::Cuda, ExecutionProvider::Cpu]
+            ),
+            base
+        );
+
+        fs::write(v2.join("encoder-model.onnx"), b"a larger graph").unwrap();
+        assert_ne!(path(&v2, &options, &cpu), base);
+    }
+
+    #[test]
+    fn joint_logits_split_after_the_vocabulary() {
+        let logits = [0.1, 0.2, 0.3, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let (vocab, durations) = split_joint_logits(&logits, 3, &DEFAULT_TDT_DURATIONS).unwrap();
+        assert_eq!(vocab, &[0.1, 0.2, 0.3]);
+        assert_eq!(durations, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        // Plain RNN-T models have no duration logits at all
+        let (vocab, durations) = split_joint_logits(&logits[..3], 3, &[0, 1]).unwrap();
+        assert_eq!(vocab.len(), 3);
+        assert!(durations.is_empty());
+    }
+
+    #[test]
+    fn duration_logit_count_must_match_the_configured_durations() {
+        let logits = [0.0; 3 + 5];
+        match split_joint_logits(&logits, 3, &[0, 1, 2, 4]) {
+            Err(ParakeetError::DurationCount { expected, found }) => {
+                assert_eq!((expected, found), (4, 5));
+            }
+            other => panic!("expected a duration count error, got {other:?}"),
+        }
+    }
+
+    fn hyp(tokens: &[i32], frame: usize, score: f32) -> Hypothesis {
+        Hypothesis {
+            decoded: DecodedSequence {
+                tokens: tokens.to_vec(),
+                ..Default::default()
+            },
+            score,
+            state: (Array3::zeros((1, 1, 1)), Array3::zeros((1, 1, 1))),
+            frame,
+        }
+    }
+
+    fn summary(hyps: &[Hypothesis]) -> Vec<(Vec<i32>, usize)> {
+        hyps.iter()
+            .map(|hyp| (hyp.decoded.tokens.clone(), hyp.frame))
+            .collect()
+    }
+
+    #[test]
+    fn candidates_follow_the_strategy() {
+        let log_probs = [-0.9, -0.1, -3.0, -0.5, -5.0];
+
+        assert_eq!(DecodingStrategy::Greedy.candidates(&log_probs), vec![1]);
+        assert_eq!(
+            DecodingStrategy::beam_search(3).candidates(&log_probs),
+            vec![1, 3, 0]
+        );
+
+        // MAES looks at beam_size + beta tokens, then drops those more than
+        // gamma below the best one
+        let maes = DecodingStrategy::Maes {
+            beam_size: 2,
+            expansion_beta: 2,
+            expansion_gamma: 1.0,
+        };
+        assert_eq!(maes.candidates(&log_probs), vec![1, 3, 0]);
+        let wide = DecodingStrategy::Maes {
+            beam_size: 2,
+            expansion_beta: 2,
+            expansion_gamma: 10.0,
+        };
+        assert_eq!(wide.candidates(&log_probs), vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn prune_merges_duplicates_with_log_sum() {
+        let quarter = 0.25f32.ln();
+        let pruned = prune(
+            vec![
+                hyp(&[7], 2, quarter),
+                hyp(&[7], 2, quarter),
+                hyp(&[7], 3, -0.1),
+                hyp(&[8], 2, -2.0),
+            ],
+            4,
+        );
+
+        assert_eq!(
+            summary(&pruned),
+            vec![(vec![7], 3), (vec![7], 2), (vec![8], 2)]
+        );
+        assert!((pruned[1].score - 0.5f32.ln()).abs() < 1e-6);
+        assert_eq!(
+            log_add(f32::NEG_INFINITY, f32::NEG_INFINITY),
+            f32::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn prune_keeps_the_best_beam_size_hypotheses() {
+        let hyps = (0..5).map(|i| hyp(&[i], 1, -(i as f32))).collect();
+        let pruned = prune(hyps, 3);
+        assert_eq!(
+            summary(&pruned),
+            vec![(vec![0], 1), (vec![1], 1), (vec![2], 1)]
+        );
+    }
+
+    #[test]
+    fn hypotheses_are_only_ranked_within_their_frame() {
+        // The frame-5 hypothesis skipped ahead on a long duration and so has
+        // a lower cumulative score, but it competes only at frame 5
+        let pruned = prune_per_frame(
+            vec![
+                hyp(&[1], 1, -1.0),
+                hyp(&[2], 1, -1.5),
+                hyp(&[3], 5, -9.0),
+                hyp(&[4], 1, -2.0),
+            ],
+            2,
+        );
+        assert_eq!(
+            summary(&pruned),
+            vec![(vec![1], 1), (vec![2], 1), (vec![3], 5)]
+        );
+    }
+
+    #[test]
+    fn beam_settles_once_every_next_frame_is_full_of_better_hypotheses() {
+        let active = [hyp(&[9], 3, -4.0)];
+        let full = prune_per_frame(
+            vec![
+                hyp(&[1], 4, -1.0),
+                hyp(&[2], 4, -2.0),
+                hyp(&[3], 5, -1.0),
+                hyp(&[4], 5, -3.0),
+            ],
+            2,
+        );
+
+        assert!(beam_is_settled(&[], &full, &[4], 2));
+        assert!(beam_is_settled(&active, &full, &next_frames(3, &[]), 2));
+        assert!(beam_is_settled(&active, &full, &next_frames(3, &[0, 2]), 2));
+        // Frame 6 has no waiting hypotheses, so the active one could still
+        // enter its beam
+        assert!(!beam_is_settled(
+            &active,
+            &full,
+            &next_frames(3, &[0, 1, 3]),
+            2
+        ));
+        // A hypothesis better than the worst one kept keeps expanding
+        assert!(!beam_is_settled(&[hyp(&[9], 3, -1.5)], &full, &[4], 2));
+        assert_eq!(next_frames(3, &[0, 1, 2]), vec![4, 5]);
+    }
+}
@@ -1,6 +1,6 @@
 use super::engine::TimestampGranularity;
 use super::model::TimestampedResult;
-use crate::TranscriptionSegment;
+use crate::{TranscriptionSegment, WordTiming};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
@@ -9,6 +9,10 @@ pub struct Token {
     pub t_start: f32,
     pub t_end: f32,
     pub is_blank: bool,
+    /// Softmax probability of the token, if the decoder reported one
+    pub probability: Option<f32>,
+    /// Entropy in nats of the distribution the token was chosen from
+    pub entropy: Option<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +21,8 @@ pub struct Word {
     pub t_start: f32,
     pub t_end: f32,
     pub tokens: Vec<Token>,
+    /// Mean probability of the word's tokens
+    pub probability: Option<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +31,8 @@ pub struct Segment {
     pub t_start: f32,
     pub t_end: f32,
     pub words: Vec<Word>,
+    /// Mean probability of all tokens in the segment
+    pub probability: Option<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -61,6 +69,20 @@ pub fn convert_timestamps(
     }
 }
 
+/// End time of token `i`: the predicted end for TDT models, otherwise the
+/// start of the next token (or a short default duration for the last token).
+fn token_end(timestamped_result: &TimestampedResult, i: usize) -> f32 {
+    if let Some(&end) = timestamped_result.end_timestamps.get(i) {
+        return end;
+    }
+    let start = timestamped_result.timestamps[i];
+    timestamped_result
+        .timestamps
+        .get(i + 1)
+        .copied()
+        .unwrap_or(start + 0.05) // Small default duration for the final token
+}
+
 // Returns the raw tokens exactly as output by the model
 fn convert_to_raw_token_segments(
     timestamped_result: &TimestampedResult,
@@ -74,16 +96,12 @@ fn convert_to_raw_token_segments(
         .enumerate()
     {
         // Include ALL tokens, even empty ones, for debugging purposes
-        let end_timestamp = timestamped_result
-            .timestamps
-            .get(i + 1)
-            .copied()
-            .unwrap_or(timestamp + 0.05); // Small default duration for tokens
-
         segments.push(TranscriptionSegment {
             start: timestamp,
-            end: end_timestamp,
+            end: token_end(timestamped_result, i),
             text: token.clone(), // Raw token text, including spaces and subword pieces
+            words: Vec::new(),
+            probability: timestamped_result.probabilities.get(i).copied(),
         });
     }
 
@@ -133,6 +151,7 @@ fn build_utterance_from_tokens(
                     t_start: 0.0,
                     t_end: 0.0,
                     words: Vec::new(),
+                    probability: None,
                 }]
             },
         };
@@ -162,18 +181,14 @@ fn create_tokens_from_timestamped_result(timestamped_result: &TimestampedResult)
         .zip(timestamped_result.timestamps.iter())
         .enumerate()
     {
-        let t_end = timestamped_result
-            .timestamps
-            .get(i + 1)
-            .copied()
-            .unwrap_or(timestamp + 0.05); // Small default duration for final token
-
         tokens.push(Token {
             text: token_text.clone(),
             token_id: Some(i),
             t_start: timestamp,
-            t_end,
+            t_end: token_end(timestamped_result, i),
             is_blank: token_text.trim().is_empty(),
+            probability: timestamped_result.probabilities.get(i).copied(),
+            entropy: timestamped_result.entropies.get(i).copied(),
         });
     }
 
@@ -225,6 +240,7 @@ fn create_word_from_tokens(tokens: &[Token]) -> Word {
             t_start: 0.0,
             t_end: 0.0,
             tokens: Vec::new(),
+            probability: None,
         };
     }
 
@@ -254,6 +270,7 @@ fn create_word_from_tokens(tokens: &[Token]) -> Word {
         t_start,
         t_end,
         tokens: tokens.to_vec(),
+        probability: mean_probability(tokens.iter()),
     }
 }
 
@@ -299,6 +316,7 @@ fn create_segment_from_words(words: &[Word]) -> Segment {
             t_start: 0.0,
             t_end: 0.0,
             words: Vec::new(),
+            probability: None,
         };
     }
 
@@ -319,6 +337,7 @@ fn create_segment_from_words(words: &[Word]) -> Segment {
         t_start,
         t_end,
         words: words.to_vec(),
+        probability: mean_probability(words.iter().flat_map(|w| &w.tokens)),
     }
 }
 
@@ -332,6 +351,8 @@ fn extract_word_segments(utterance: &Utterance) -> Vec<TranscriptionSegment> {
                     start: word.t_start,
                     end: word.t_end,
                     text: word.text.clone(),
+                    words: Vec::new(),
+                    probability: word.probability,
                 });
             }
         }
@@ -349,10 +370,34 @@ fn extract_segment_segments(utterance: &Utterance) -> Vec<TranscriptionSegment>
             start: segment.t_start,
             end: segment.t_end,
             text: segment.text.clone(),
+            words: segment
+                .words
+                .iter()
+                .filter(|word| !word.text.trim().is_empty())
+                .map(word_timing)
+                .collect(),
+            probability: segment.probability,
         })
         .collect()
 }
 
+fn word_timing(word: &Word) -> WordTiming {
+    WordTiming {
+        text: word.text.clone(),
+        start: word.t_start,
+        end: word.t_end,
+        probability: word.probability,
+    }
+}
+
+/// Mean probability of `tokens`, or `None` if none of them has one.
+fn mean_probability<'a>(tokens: impl Iterator<Item = &'a Token>) -> Option<f32> {
+    let (sum, count) = tokens
+        .filter_map(|token| token.probability)
+        .fold((0.0, 0), |(sum, count), p| (sum + p, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
 // Legacy functions for backward compatibility
 pub fn group_tokens_into_words(timestamped_result: &TimestampedResult) -> Vec<WordBoundary> {
     let words = convert_to_hierarchical_word_segments(timestamped_result);
@@ -0,0 +1,113 @@
+//! Checking model files before loading them.
+//!
+//! Loading a model surfaces problems one at a time, often as a bare error
+//! from the inference library. Each engine's `validate_model` function
+//! instead inspects every file the engine needs and collects all problems
+//! into a [`ModelValidation`] report, without loading anything.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A problem found while validating a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelProblem {
+    /// A required file or directory does not exist.
+    Missing(PathBuf),
+    /// A file exists but could not be read.
+    Unreadable {
+        /// The file that could not be read
+        path: PathBuf,
+        /// Description of the I/O error
+        reason: String,
+    },
+    /// A file was read but its contents are not what the engine expects.
+    InvalidContents {
+        /// The offending file
+        path: PathBuf,
+        /// Description of the problem
+        reason: String,
+    },
+}
+
+impl fmt::Display for ModelProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelProblem::Missing(path) => write!(f, "{} is missing", path.display()),
+            ModelProblem::Unreadable { path, reason } => {
+                write!(f, "{} cannot be read: {}", path.display(), reason)
+            }
+            ModelProblem::InvalidContents { path, reason } => {
+                write!(f, "{} is invalid: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+/// Report produced by validating a model path.
+///
+/// # Examples
+///
+/// ```rust
+/// use transcribe_rs::engines::whisper;
+/// use std::path::Path;
+///
+/// let report = whisper::validate_model(Path::new("models/missing.bin"));
+/// assert!(!report.is_valid());
+/// for problem in &report.problems {
+///     eprintln!("{problem}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelValidation {
+    /// The model file or directory that was validated
+    pub path: PathBuf,
+    /// Every problem found; empty if the model looks loadable
+    pub problems: Vec<ModelProblem>,
+}
+
+impl ModelValidation {
+    pub(crate) fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            problems: Vec::new(),
+        }
+    }
+
+    /// Whether no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    pub(crate) fn missing(&mut self, path: PathBuf) {
+        self.problems.push(ModelProblem::Missing(path));
+    }
+
+    pub(crate) fn unreadable(&mut self, path: PathBuf, err: std::io::Error) {
+        self.problems.push(ModelProblem::Unreadable {
+            path,
+            reason: err.to_string(),
+        });
+    }
+
+    pub(crate) fn invalid(&mut self, path: PathBuf, reason: impl Into<String>) {
+        self.problems.push(ModelProblem::InvalidContents {
+            path,
+            reason: reason.into(),
+        });
+    }
+}
+
+impl fmt::Display for ModelValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "{} is valid", self.path.display());
+        }
+        for (i, problem) in self.problems.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
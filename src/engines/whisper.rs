@@ -55,18 +55,206 @@
 //! println!("Transcription: {}", result.text);
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! ## CPU-only Inference
+//!
+//! ```rust,no_run
+//! use transcribe_rs::{TranscriptionEngine, engines::whisper::{WhisperEngine, WhisperInferenceParams, WhisperModelParams}};
+//! use std::path::PathBuf;
+//!
+//! let mut engine = WhisperEngine::new();
+//! engine.load_model_with_params(
+//!     &PathBuf::from("models/whisper-medium-q4_1.bin"),
+//!     WhisperModelParams {
+//!         use_gpu: false,
+//!         ..Default::default()
+//!     },
+//! )?;
+//!
+//! let params = WhisperInferenceParams {
+//!     n_threads: Some(2),
+//!     ..Default::default()
+//! };
+//!
+//! let result = engine.transcribe_file(&PathBuf::from("audio.wav"), Some(params))?;
+//! println!("Transcription: {}", result.text);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
-use crate::{TranscriptionEngine, TranscriptionResult, TranscriptionSegment};
+use crate::{
+    engines::ModelValidation, CancellationToken, ProgressObserver, TimestampGranularity,
+    TranscribeError, TranscribeOptions, TranscriptionEngine, TranscriptionResult,
+    TranscriptionSegment, TranscriptionTask, WordTiming,
+};
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_int, c_void, CStr};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use whisper_rs::whisper_rs_sys::{self, whisper_context, whisper_state};
+use whisper_rs::{
+    DtwMode, DtwModelPreset, FullParams, SamplingStrategy, WhisperContext,
+    WhisperContextParameters, WhisperState, WhisperToken,
+};
+
+mod pool;
+
+pub use pool::WhisperPool;
+
+/// Whisper model architectures with known alignment heads, used to enable
+/// DTW token timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhisperDtwPreset {
+    /// Whisper `tiny.en`
+    TinyEn,
+    /// Whisper `tiny`
+    Tiny,
+    /// Whisper `base.en`
+    BaseEn,
+    /// Whisper `base`
+    Base,
+    /// Whisper `small.en`
+    SmallEn,
+    /// Whisper `small`
+    Small,
+    /// Whisper `medium.en`
+    MediumEn,
+    /// Whisper `medium`
+    Medium,
+    /// Whisper `large-v1`
+    LargeV1,
+    /// Whisper `large-v2`
+    LargeV2,
+    /// Whisper `large-v3`
+    LargeV3,
+}
+
+impl WhisperDtwPreset {
+    fn to_whisper(self) -> DtwModelPreset {
+        match self {
+            WhisperDtwPreset::TinyEn => DtwModelPreset::TinyEn,
+            WhisperDtwPreset::Tiny => DtwModelPreset::Tiny,
+            WhisperDtwPreset::BaseEn => DtwModelPreset::BaseEn,
+            WhisperDtwPreset::Base => DtwModelPreset::Base,
+            WhisperDtwPreset::SmallEn => DtwModelPreset::SmallEn,
+            WhisperDtwPreset::Small => DtwModelPreset::Small,
+            WhisperDtwPreset::MediumEn => DtwModelPreset::MediumEn,
+            WhisperDtwPreset::Medium => DtwModelPreset::Medium,
+            WhisperDtwPreset::LargeV1 => DtwModelPreset::LargeV1,
+            WhisperDtwPreset::LargeV2 => DtwModelPreset::LargeV2,
+            WhisperDtwPreset::LargeV3 => DtwModelPreset::LargeV3,
+        }
+    }
+}
 
 /// Parameters for configuring Whisper model loading.
-///
-/// Currently, Whisper model loading doesn't require additional parameters
-/// beyond the model file path. This struct exists for API consistency
-/// and future extensibility.
-#[derive(Debug, Clone, Default)]
-pub struct WhisperModelParams {}
+#[derive(Debug, Clone)]
+pub struct WhisperModelParams {
+    /// Whether to run on the GPU when whisper.cpp was built with a GPU
+    /// backend (Metal, Vulkan, ...). Defaults to whisper-rs's own default,
+    /// which is only `true` when one of its GPU features is enabled. Set to
+    /// `false` to force CPU inference, e.g. for deterministic tests.
+    pub use_gpu: bool,
+
+    /// Index of the GPU to use when several are available.
+    pub gpu_device: usize,
+
+    /// Whether to use flash attention, which is faster on supported GPUs.
+    /// whisper.cpp does not support DTW alignment together with flash
+    /// attention, so loading fails when [`dtw_preset`](Self::dtw_preset) is
+    /// also set.
+    pub flash_attn: bool,
+
+    /// Enable DTW-based token alignment using the attention heads of the
+    /// given architecture, which must match the loaded model. Improves
+    /// word and token timestamps at a small cost in speed and memory.
+    pub dtw_preset: Option<WhisperDtwPreset>,
+}
+
+impl Default for WhisperModelParams {
+    fn default() -> Self {
+        Self {
+            use_gpu: WhisperContextParameters::default().use_gpu,
+            gpu_device: 0,
+            flash_attn: false,
+            dtw_preset: None,
+        }
+    }
+}
+
+/// Decoding strategy used by Whisper.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WhisperSamplingStrategy {
+    /// Pick the most likely token at each step. When decoding falls back to a
+    /// higher temperature, `best_of` candidates are sampled and the best kept.
+    Greedy {
+        /// Number of candidates sampled at non-zero temperature
+        best_of: usize,
+    },
+    /// Keep the `beam_size` most likely hypotheses at each step.
+    BeamSearch {
+        /// Number of hypotheses kept at each step
+        beam_size: usize,
+        /// Beam search patience factor; `-1.0` uses whisper.cpp's default
+        patience: f32,
+    },
+}
+
+impl WhisperSamplingStrategy {
+    /// Greedy decoding, the lowest-latency option.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::whisper::WhisperSamplingStrategy;
+    ///
+    /// let strategy = WhisperSamplingStrategy::greedy();
+    /// ```
+    pub fn greedy() -> Self {
+        Self::Greedy { best_of: 5 }
+    }
+
+    /// Beam search with `beam_size` hypotheses and the default patience.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::whisper::WhisperSamplingStrategy;
+    ///
+    /// let strategy = WhisperSamplingStrategy::beam_search(5);
+    /// ```
+    pub fn beam_search(beam_size: usize) -> Self {
+        Self::BeamSearch {
+            beam_size,
+            patience: -1.0,
+        }
+    }
+
+    fn to_whisper(&self) -> SamplingStrategy {
+        match *self {
+            WhisperSamplingStrategy::Greedy { best_of } => SamplingStrategy::Greedy {
+                best_of: to_c_int(best_of),
+            },
+            WhisperSamplingStrategy::BeamSearch {
+                beam_size,
+                patience,
+            } => SamplingStrategy::BeamSearch {
+                beam_size: to_c_int(beam_size),
+                patience,
+            },
+        }
+    }
+}
+
+impl Default for WhisperSamplingStrategy {
+    fn default() -> Self {
+        Self::beam_search(3)
+    }
+}
 
 /// Parameters for configuring Whisper inference behavior.
 ///
@@ -78,6 +266,10 @@ pub struct WhisperInferenceParams {
     /// If None, Whisper will auto-detect the language.
     pub language: Option<String>,
 
+    /// Whether to transcribe the speech or translate it into English.
+    /// Translation needs a multilingual model (not an `.en` model).
+    pub task: TranscriptionTask,
+
     /// Whether to print special tokens in the output
     pub print_special: bool,
 
@@ -98,12 +290,81 @@ pub struct WhisperInferenceParams {
 
     /// Threshold for detecting silence/no-speech segments (0.0-1.0).
     pub no_speech_thold: f32,
+
+    /// Greedy or beam search decoding (default: beam search with 3 beams)
+    pub sampling_strategy: WhisperSamplingStrategy,
+
+    /// Initial decoding temperature. `0.0` decodes deterministically.
+    pub temperature: f32,
+
+    /// Amount the temperature is raised when a decoding attempt fails the
+    /// entropy or log probability thresholds. `0.0` disables the fallback.
+    pub temperature_inc: f32,
+
+    /// Decoding is retried at a higher temperature when the entropy of the
+    /// output exceeds this value (similar to a compression ratio threshold).
+    pub entropy_thold: f32,
+
+    /// Decoding is retried at a higher temperature when the average token
+    /// log probability falls below this value.
+    pub logprob_thold: f32,
+
+    /// Text used to prime the decoder, e.g. a list of product names or a
+    /// sample of the expected style. Whisper keeps only the most recent
+    /// ~224 tokens of the prompt.
+    pub initial_prompt: Option<String>,
+
+    /// Whether to ignore text decoded by earlier calls on the same engine.
+    /// Set to `false` to let Whisper condition each call on the previous
+    /// call's transcript, which keeps terminology consistent across
+    /// sequential chunks.
+    pub no_context: bool,
+
+    /// Granularity of the returned segments. `Segment` (the default) returns
+    /// Whisper's phrase-level segments; `Word` and `Token` return one segment
+    /// per word or per text token, with timings from token timestamps (or DTW
+    /// alignment, see [`WhisperModelParams::dtw_preset`]) and probabilities.
+    pub timestamp_granularity: TimestampGranularity,
+
+    /// Whether to compute per-token timestamps and fill in
+    /// [`TranscriptionSegment::words`] with word timings and probabilities.
+    /// Only used with [`TimestampGranularity::Segment`].
+    pub word_timestamps: bool,
+
+    /// Maximum length of a segment in characters, e.g. `42` for subtitles.
+    /// Longer segments are split using token timestamps. `None` leaves
+    /// segment length to Whisper; `Some(0)` is rejected.
+    pub max_segment_len: Option<usize>,
+
+    /// Whether [`max_segment_len`](Self::max_segment_len) splits only at word
+    /// boundaries instead of between any two tokens.
+    pub split_on_word: bool,
+
+    /// Maximum number of text tokens per segment. `None` means no limit;
+    /// `Some(0)` is rejected.
+    pub max_tokens: Option<usize>,
+
+    /// Whether to force the whole input into a single segment, which is
+    /// useful for short realtime windows.
+    pub single_segment: bool,
+
+    /// Number of CPU threads used for inference. `None` uses whisper.cpp's
+    /// default of up to four threads; `Some(0)` is rejected.
+    pub n_threads: Option<usize>,
+
+    /// Observer notified of whisper.cpp's progress and of each segment as
+    /// it is decoded.
+    pub progress: Option<Arc<dyn ProgressObserver>>,
+
+    /// Token that aborts the run with [`TranscribeError::Cancelled`] when cancelled.
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl Default for WhisperInferenceParams {
     fn default() -> Self {
         Self {
             language: None,
+            task: TranscriptionTask::Transcribe,
             print_special: false,
             print_progress: false,
             print_realtime: false,
@@ -111,6 +372,123 @@ impl Default for WhisperInferenceParams {
             suppress_blank: true,
             suppress_non_speech_tokens: true,
             no_speech_thold: 0.2,
+            sampling_strategy: WhisperSamplingStrategy::default(),
+            temperature: 0.0,
+            temperature_inc: 0.2,
+            entropy_thold: 2.4,
+            logprob_thold: -1.0,
+            initial_prompt: None,
+            no_context: true,
+            timestamp_granularity: TimestampGranularity::Segment,
+            word_timestamps: false,
+            max_segment_len: None,
+            split_on_word: false,
+            max_tokens: None,
+            single_segment: false,
+            n_threads: None,
+            progress: None,
+            cancellation: None,
+        }
+    }
+}
+
+impl WhisperInferenceParams {
+    /// Reject limits of zero, which whisper.cpp would read as "no limit".
+    fn validate(&self) -> Result<(), TranscribeError> {
+        for (name, value) in [
+            ("max_segment_len", self.max_segment_len),
+            ("max_tokens", self.max_tokens),
+            ("n_threads", self.n_threads),
+        ] {
+            if value == Some(0) {
+                return Err(TranscribeError::InvalidParameter(format!(
+                    "{name} must be at least 1"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum number of characters of the preceding transcript that
+/// [`WhisperInferenceParams::with_previous_text`] adds to the prompt.
+///
+/// Whisper keeps only the last ~224 prompt tokens, so the tail is bounded to
+/// roughly half of that to leave room for the initial prompt in front of it.
+pub const PREVIOUS_TEXT_MAX_CHARS: usize = 400;
+
+impl WhisperInferenceParams {
+    /// Return a copy of these parameters whose prompt continues from
+    /// `previous_text`, the transcript of the preceding chunk.
+    ///
+    /// Only the last [`PREVIOUS_TEXT_MAX_CHARS`] characters of the previous
+    /// text are used, cut at a word boundary where possible. Any
+    /// [`initial_prompt`](Self::initial_prompt) is kept in front of them, so
+    /// domain vocabulary is still applied as long as the initial prompt
+    /// itself fits in the rest of Whisper's prompt window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::engines::whisper::WhisperInferenceParams;
+    ///
+    /// let params = WhisperInferenceParams {
+    ///     initial_prompt: Some("Kubernetes, gRPC, Tokio.".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let next = params.with_previous_text("We moved the scheduler to Tokio.");
+    /// assert_eq!(
+    ///     next.initial_prompt.as_deref(),
+    ///     Some("Kubernetes, gRPC, Tokio. We moved the scheduler to Tokio.")
+    /// );
+    /// ```
+    pub fn with_previous_text(&self, previous_text: &str) -> Self {
+        let previous_text = previous_text_tail(previous_text.trim());
+        let initial_prompt = match self.initial_prompt.as_deref().map(str::trim) {
+            Some(prompt) if !prompt.is_empty() && !previous_text.is_empty() => {
+                Some(format!("{prompt} {previous_text}"))
+            }
+            Some(prompt) if !prompt.is_empty() => Some(prompt.to_string()),
+            _ if !previous_text.is_empty() => Some(previous_text.to_string()),
+            _ => None,
+        };
+
+        Self {
+            initial_prompt,
+            ..self.clone()
+        }
+    }
+}
+
+/// The last [`PREVIOUS_TEXT_MAX_CHARS`] characters of `text`, starting at a
+/// word boundary unless the tail is a single word.
+fn previous_text_tail(text: &str) -> &str {
+    let Some((start, _)) = text.char_indices().rev().nth(PREVIOUS_TEXT_MAX_CHARS - 1) else {
+        return text;
+    };
+    let tail = &text[start..];
+    if text[..start].ends_with(char::is_whitespace) {
+        return tail;
+    }
+    match tail.find(char::is_whitespace) {
+        Some(space) => tail[space..].trim_start(),
+        None => tail,
+    }
+}
+
+impl From<&TranscribeOptions> for WhisperInferenceParams {
+    fn from(options: &TranscribeOptions) -> Self {
+        Self {
+            language: options.language.clone(),
+            task: options.task,
+            timestamp_granularity: options
+                .timestamp_granularity
+                .clone()
+                .unwrap_or(TimestampGranularity::Segment),
+            n_threads: options.n_threads,
+            progress: options.progress.clone(),
+            cancellation: options.cancellation.clone(),
+            ..Default::default()
         }
     }
 }
@@ -167,6 +545,102 @@ impl WhisperEngine {
             context: None,
         }
     }
+
+    /// Load a model from GGML data held in memory.
+    ///
+    /// Useful when the model is shipped inside an encrypted or compressed
+    /// bundle and should never be written to disk. The data is copied by
+    /// whisper.cpp, so `model_bytes` can be dropped (or zeroed) once this
+    /// returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use transcribe_rs::engines::whisper::{WhisperEngine, WhisperModelParams};
+    ///
+    /// let model_bytes: Vec<u8> = std::fs::read("models/whisper-medium-q4_1.bin")?;
+    ///
+    /// let mut engine = WhisperEngine::new();
+    /// engine.load_model_from_bytes(&model_bytes, WhisperModelParams::default())?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load_model_from_bytes(
+        &mut self,
+        model_bytes: &[u8],
+        params: WhisperModelParams,
+    ) -> Result<(), TranscribeError> {
+        let context = context_from_bytes(model_bytes, &params)?;
+        let state = context.create_state()?;
+
+        self.context = Some(context);
+        self.state = Some(state);
+        self.loaded_model_path = None;
+        Ok(())
+    }
+
+    /// Detect the spoken language of `samples` (16 kHz mono).
+    ///
+    /// Only the first 30 seconds are considered, and the result is only
+    /// meaningful for multilingual models. `n_threads` is the number of CPU
+    /// threads to use, as in [`WhisperInferenceParams::n_threads`]; `None`
+    /// uses whisper.cpp's default of up to four threads. `Some(0)` and empty
+    /// `samples` are rejected.
+    ///
+    /// # Returns
+    ///
+    /// Returns every language Whisper knows as `(code, probability)` pairs,
+    /// most likely first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use transcribe_rs::{TranscriptionEngine, engines::whisper::WhisperEngine};
+    /// use std::path::Path;
+    ///
+    /// let mut engine = WhisperEngine::new();
+    /// engine.load_model(Path::new("models/whisper-medium-q4_1.bin"))?;
+    ///
+    /// let samples = transcribe_rs::audio::read_audio_samples(Path::new("audio.wav"))?;
+    /// if let Some((language, probability)) = engine.detect_language(&samples, None)?.first() {
+    ///     println!("{language} ({:.0}%)", probability * 100.0);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn detect_language(
+        &mut self,
+        samples: &[f32],
+        n_threads: Option<usize>,
+    ) -> Result<Vec<(String, f32)>, TranscribeError> {
+        if n_threads == Some(0) {
+            return Err(TranscribeError::InvalidParameter(
+                "n_threads must be at least 1".to_string(),
+            ));
+        }
+        if samples.is_empty() {
+            return Err(TranscribeError::unsupported_audio(
+                "no samples to detect the language of",
+            ));
+        }
+
+        let state = self.state.as_mut().ok_or(TranscribeError::ModelNotLoaded)?;
+        let threads = n_threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get().min(4)));
+
+        state.pcm_to_mel(samples, threads)?;
+        let (_, probabilities) = state.lang_detect(0, threads)?;
+
+        let mut languages: Vec<(String, f32)> = probabilities
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, probability)| {
+                let code = whisper_rs::get_lang_str(id as i32)?;
+                Some((code.to_string(), probability))
+            })
+            .collect();
+        languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Ok(languages)
+    }
 }
 
 impl Drop for WhisperEngine {
@@ -182,14 +656,9 @@ impl TranscriptionEngine for WhisperEngine {
     fn load_model_with_params(
         &mut self,
         model_path: &Path,
-        _params: Self::ModelParams,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // Create new context and state following your working pattern
-        let context = WhisperContext::new_with_params(
-            model_path.to_str().unwrap(),
-            WhisperContextParameters::default(),
-        )?;
-
+        params: Self::ModelParams,
+    ) -> Result<(), TranscribeError> {
+        let context = load_context(model_path, params)?;
         let state = context.create_state()?;
 
         self.context = Some(context);
@@ -205,56 +674,483 @@ impl TranscriptionEngine for WhisperEngine {
         self.context = None;
     }
 
+    fn supports_task(&self, _task: TranscriptionTask) -> bool {
+        true
+    }
+
     fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
         params: Option<Self::InferenceParams>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
-        let state = self
-            .state
-            .as_mut()
-            .ok_or("Model not loaded. Call load_model() first.")?;
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let params = params.unwrap_or_default();
+        params.validate()?;
+        if params
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(TranscribeError::Cancelled);
+        }
+        let state = self.state.as_mut().ok_or(TranscribeError::ModelNotLoaded)?;
+        let context = self
+            .context
+            .as_ref()
+            .ok_or(TranscribeError::ModelNotLoaded)?;
 
-        let whisper_params = params.unwrap_or_default();
+        transcribe_with_state(context, state, &samples, params)
+    }
+}
 
-        let mut full_params = FullParams::new(SamplingStrategy::BeamSearch {
-            beam_size: 3,
-            patience: -1.0,
-        });
-        full_params.set_language(whisper_params.language.as_deref());
-        full_params.set_print_special(whisper_params.print_special);
-        full_params.set_print_progress(whisper_params.print_progress);
-        full_params.set_print_realtime(whisper_params.print_realtime);
-        full_params.set_print_timestamps(whisper_params.print_timestamps);
-        full_params.set_suppress_blank(whisper_params.suppress_blank);
-        full_params.set_suppress_non_speech_tokens(whisper_params.suppress_non_speech_tokens);
-        full_params.set_no_speech_thold(whisper_params.no_speech_thold);
-
-        state.full(full_params, &samples)?;
-
-        let num_segments = state
-            .full_n_segments()
-            .expect("failed to get number of segments");
-
-        let mut segments = Vec::new();
-        let mut full_text = String::new();
-
-        for i in 0..num_segments {
-            let text = state.full_get_segment_text(i)?;
-            let start = state.full_get_segment_t0(i)? as f32 / 100.0;
-            let end = state.full_get_segment_t1(i)? as f32 / 100.0;
-
-            segments.push(TranscriptionSegment {
+/// Magic number at the start of every GGML model file.
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+
+/// Check that `model_path` is a readable GGML Whisper model without loading it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use transcribe_rs::engines::whisper;
+/// use std::path::Path;
+///
+/// let report = whisper::validate_model(Path::new("models/whisper-medium-q4_1.bin"));
+/// if !report.is_valid() {
+///     eprintln!("Cannot use model: {report}");
+/// }
+/// ```
+pub fn validate_model(model_path: &Path) -> ModelValidation {
+    let mut report = ModelValidation::new(model_path);
+    if !model_path.is_file() {
+        report.missing(model_path.to_path_buf());
+        return report;
+    }
+
+    let mut magic = [0u8; 4];
+    match File::open(model_path).and_then(|mut file| file.read_exact(&mut magic)) {
+        Ok(()) if u32::from_le_bytes(magic) == GGML_MAGIC => {}
+        Ok(()) => report.invalid(
+            model_path.to_path_buf(),
+            "not a GGML model (unexpected file header)",
+        ),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => report.invalid(
+            model_path.to_path_buf(),
+            "file is too short to be a GGML model",
+        ),
+        Err(err) => report.unreadable(model_path.to_path_buf(), err),
+    }
+
+    report
+}
+
+/// Load a Whisper model held in memory into a new context.
+///
+/// Buffers without the GGML header are rejected up front, with the same
+/// error whisper.cpp reports for them, instead of being handed to it.
+fn context_from_bytes(
+    model_bytes: &[u8],
+    params: &WhisperModelParams,
+) -> Result<WhisperContext, TranscribeError> {
+    let context_params = context_params(params)?;
+    if model_bytes.get(..4) != Some(&GGML_MAGIC.to_le_bytes()[..]) {
+        return Err(whisper_rs::WhisperError::InitError.into());
+    }
+    Ok(WhisperContext::new_from_buffer_with_params(
+        model_bytes,
+        context_params,
+    )?)
+}
+
+/// Load a Whisper model file into a new context.
+fn load_context(
+    model_path: &Path,
+    params: WhisperModelParams,
+) -> Result<WhisperContext, TranscribeError> {
+    let context_params = context_params(&params)?;
+    if !model_path.is_file() {
+        return Err(TranscribeError::ModelFileMissing(model_path.to_path_buf()));
+    }
+    let report = validate_model(model_path);
+    if !report.is_valid() {
+        return Err(TranscribeError::InvalidModel(report));
+    }
+
+    let context = match model_path.to_str() {
+        Some(path) => WhisperContext::new_with_params(path, context_params)?,
+        // whisper-rs only accepts UTF-8 paths, so load other paths from memory
+        None => {
+            WhisperContext::new_from_buffer_with_params(&fs::read(model_path)?, context_params)?
+        }
+    };
+    Ok(context)
+}
+
+/// Translate model parameters into whisper-rs context parameters.
+fn context_params(
+    params: &WhisperModelParams,
+) -> Result<WhisperContextParameters<'static>, TranscribeError> {
+    if params.flash_attn && params.dtw_preset.is_some() {
+        return Err(TranscribeError::InvalidParameter(
+            "dtw_preset cannot be used together with flash_attn".to_string(),
+        ));
+    }
+    let mut context_params = WhisperContextParameters {
+        use_gpu: params.use_gpu,
+        gpu_device: to_c_int(params.gpu_device),
+        flash_attn: params.flash_attn,
+        ..Default::default()
+    };
+    if let Some(preset) = params.dtw_preset {
+        context_params.dtw_parameters.mode = DtwMode::ModelPreset {
+            model_preset: preset.to_whisper(),
+        };
+    }
+    Ok(context_params)
+}
+
+/// Run Whisper on `samples` with `state` and collect the result.
+fn transcribe_with_state(
+    context: &WhisperContext,
+    state: &mut WhisperState,
+    samples: &[f32],
+    whisper_params: WhisperInferenceParams,
+) -> Result<TranscriptionResult, TranscribeError> {
+    let mut full_params = FullParams::new(whisper_params.sampling_strategy.to_whisper());
+    full_params.set_language(whisper_params.language.as_deref());
+    full_params.set_translate(whisper_params.task == TranscriptionTask::Translate);
+    full_params.set_print_special(whisper_params.print_special);
+    full_params.set_print_progress(whisper_params.print_progress);
+    full_params.set_print_realtime(whisper_params.print_realtime);
+    full_params.set_print_timestamps(whisper_params.print_timestamps);
+    full_params.set_suppress_blank(whisper_params.suppress_blank);
+    full_params.set_suppress_non_speech_tokens(whisper_params.suppress_non_speech_tokens);
+    full_params.set_no_speech_thold(whisper_params.no_speech_thold);
+    full_params.set_temperature(whisper_params.temperature);
+    full_params.set_temperature_inc(whisper_params.temperature_inc);
+    full_params.set_entropy_thold(whisper_params.entropy_thold);
+    full_params.set_logprob_thold(whisper_params.logprob_thold);
+    full_params.set_no_context(whisper_params.no_context);
+    if let Some(prompt) = &whisper_params.initial_prompt {
+        // whisper-rs panics on interior NUL bytes
+        full_params.set_initial_prompt(&prompt.replace('\0', ""));
+    }
+    // whisper.cpp splits long segments using token timestamps
+    full_params.set_token_timestamps(
+        whisper_params.word_timestamps
+            || whisper_params.max_segment_len.is_some()
+            || whisper_params.timestamp_granularity != TimestampGranularity::Segment,
+    );
+    if let Some(max_len) = whisper_params.max_segment_len {
+        full_params.set_max_len(to_c_int(max_len));
+    }
+    full_params.set_split_on_word(whisper_params.split_on_word);
+    if let Some(max_tokens) = whisper_params.max_tokens {
+        full_params.set_max_tokens(to_c_int(max_tokens));
+    }
+    full_params.set_single_segment(whisper_params.single_segment);
+    if let Some(n_threads) = whisper_params.n_threads {
+        full_params.set_n_threads(to_c_int(n_threads));
+    }
+
+    // whisper.cpp calls back into this for the duration of `full`, so it
+    // must stay alive until it returns.
+    let observer: Option<&dyn ProgressObserver> = whisper_params.progress.as_deref();
+    let callbacks = CallbackData {
+        observer,
+        cancelled: whisper_params
+            .cancellation
+            .as_ref()
+            .map(CancellationToken::flag),
+        panic: RefCell::new(None),
+    };
+    if callbacks.should_abort() {
+        return Err(TranscribeError::Cancelled);
+    }
+
+    let user_data = &callbacks as *const CallbackData as *mut c_void;
+    // SAFETY: `user_data` points to `callbacks`, which outlives `state.full`
+    // below, and the trampolines only read it as `&CallbackData`.
+    unsafe {
+        if observer.is_some() {
+            full_params.set_progress_callback(Some(progress_trampoline));
+            full_params.set_progress_callback_user_data(user_data);
+            full_params.set_new_segment_callback(Some(segment_trampoline));
+            full_params.set_new_segment_callback_user_data(user_data);
+        }
+        full_params.set_abort_callback(Some(abort_trampoline));
+        full_params.set_abort_callback_user_data(user_data);
+        full_params.set_start_encoder_callback(Some(encoder_begin_trampoline));
+        full_params.set_start_encoder_callback_user_data(user_data);
+    }
+
+    let outcome = state.full(full_params, samples);
+    // Unwinding through whisper.cpp is undefined behaviour, so a panicking
+    // observer stops the run and its panic is raised again here
+    if let Some(payload) = callbacks.panic.take() {
+        panic::resume_unwind(payload);
+    }
+    if callbacks.should_abort() {
+        return Err(TranscribeError::Cancelled);
+    }
+    outcome?;
+
+    let num_segments = state.full_n_segments()?;
+
+    let mut segments = Vec::new();
+    let mut full_text = String::new();
+
+    for i in 0..num_segments {
+        let text = state.full_get_segment_text(i)?;
+        let start = state.full_get_segment_t0(i)? as f32 / 100.0;
+        let end = state.full_get_segment_t1(i)? as f32 / 100.0;
+        let tokens = collect_segment_tokens(state, i, context.token_eot(), end)?;
+        full_text.push_str(&text);
+
+        match whisper_params.timestamp_granularity {
+            TimestampGranularity::Segment => segments.push(TranscriptionSegment {
                 start,
                 end,
-                text: text.clone(),
-            });
-            full_text.push_str(&text);
+                text,
+                words: if whisper_params.word_timestamps {
+                    group_words(&tokens)
+                } else {
+                    Vec::new()
+                },
+                probability: mean_probability(&tokens),
+            }),
+            TimestampGranularity::Word => segments.extend(group_words(&tokens).into_iter().map(
+                |word| TranscriptionSegment {
+                    start: word.start,
+                    end: word.end,
+                    text: word.text,
+                    words: Vec::new(),
+                    probability: word.probability,
+                },
+            )),
+            TimestampGranularity::Token => {
+                segments.extend(tokens.into_iter().map(|token| TranscriptionSegment {
+                    start: token.start,
+                    end: token.end,
+                    text: token.text,
+                    words: Vec::new(),
+                    probability: Some(token.probability),
+                }))
+            }
+        }
+    }
+
+    if let Some(observer) = observer {
+        observer.on_progress(100.0);
+    }
+
+    let language = whisper_rs::get_lang_str(state.full_lang_id_from_state()?)
+        .map(str::to_string)
+        .or(whisper_params.language);
+
+    Ok(TranscriptionResult {
+        text: full_text.trim().to_string(),
+        segments,
+        language,
+    })
+}
+
+/// Convert a count to a C int, saturating at `c_int::MAX`.
+fn to_c_int(value: usize) -> c_int {
+    c_int::try_from(value).unwrap_or(c_int::MAX)
+}
+
+/// What the whisper.cpp callbacks of one run need, passed as `user_data`.
+struct CallbackData<'a> {
+    observer: Option<&'a dyn ProgressObserver>,
+    cancelled: Option<&'a AtomicBool>,
+    /// Payload of a panic raised by the observer
+    panic: RefCell<Option<Box<dyn Any + Send>>>,
+}
+
+impl CallbackData<'_> {
+    /// Whether the run was cancelled or the observer panicked.
+    fn should_abort(&self) -> bool {
+        self.cancelled
+            .is_some_and(|flag| flag.load(Ordering::SeqCst))
+            || self.panic.borrow().is_some()
+    }
+
+    /// Call the observer, if any, catching a panic instead of letting it
+    /// unwind into whisper.cpp. The observer is not called again after it
+    /// has panicked.
+    fn notify(&self, call: impl FnOnce(&dyn ProgressObserver)) {
+        let Some(observer) = self.observer else {
+            return;
+        };
+        if self.panic.borrow().is_some() {
+            return;
+        }
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| call(observer))) {
+            *self.panic.borrow_mut() = Some(payload);
+        }
+    }
+}
+
+/// Forwards whisper.cpp progress to the observer in the `CallbackData`
+/// behind `user_data`.
+unsafe extern "C" fn progress_trampoline(
+    _ctx: *mut whisper_context,
+    _state: *mut whisper_state,
+    progress: c_int,
+    user_data: *mut c_void,
+) {
+    let callbacks = unsafe { &*(user_data as *const CallbackData) };
+    callbacks.notify(|observer| observer.on_progress(progress as f32));
+}
+
+/// Forwards newly decoded segments to the observer in the `CallbackData`
+/// behind `user_data`.
+unsafe extern "C" fn segment_trampoline(
+    _ctx: *mut whisper_context,
+    state: *mut whisper_state,
+    n_new: c_int,
+    user_data: *mut c_void,
+) {
+    let callbacks = unsafe { &*(user_data as *const CallbackData) };
+    let n_segments = unsafe { whisper_rs_sys::whisper_full_n_segments_from_state(state) };
+
+    for i in (n_segments - n_new).max(0)..n_segments {
+        let (text, t0, t1) = unsafe {
+            let text = whisper_rs_sys::whisper_full_get_segment_text_from_state(state, i);
+            if text.is_null() {
+                continue;
+            }
+            (
+                CStr::from_ptr(text).to_string_lossy().into_owned(),
+                whisper_rs_sys::whisper_full_get_segment_t0_from_state(state, i),
+                whisper_rs_sys::whisper_full_get_segment_t1_from_state(state, i),
+            )
+        };
+
+        let segment = TranscriptionSegment {
+            start: t0 as f32 / 100.0,
+            end: t1 as f32 / 100.0,
+            text,
+            words: Vec::new(),
+            probability: None,
+        };
+        callbacks.notify(|observer| observer.on_segment(&segment));
+    }
+}
+
+/// Asks whisper.cpp to abort once the `CallbackData` behind `user_data` is
+/// cancelled or its observer has panicked.
+unsafe extern "C" fn abort_trampoline(user_data: *mut c_void) -> bool {
+    unsafe { &*(user_data as *const CallbackData) }.should_abort()
+}
+
+/// Skips the encoder once the `CallbackData` behind `user_data` is cancelled
+/// or its observer has panicked.
+unsafe extern "C" fn encoder_begin_trampoline(
+    _ctx: *mut whisper_context,
+    _state: *mut whisper_state,
+    user_data: *mut c_void,
+) -> bool {
+    !unsafe { &*(user_data as *const CallbackData) }.should_abort()
+}
+
+/// Timing and probability of a single text token.
+struct TokenTiming {
+    text: String,
+    start: f32,
+    end: f32,
+    probability: f32,
+}
+
+/// Read the text tokens of a segment, skipping special tokens.
+///
+/// When the model was loaded with DTW alignment, each token is timed from its
+/// DTW timestamp to the next token's (or `segment_end` for the last one),
+/// which is more accurate than whisper.cpp's default token timestamps.
+fn collect_segment_tokens(
+    state: &WhisperState,
+    segment: i32,
+    token_eot: WhisperToken,
+    segment_end: f32,
+) -> Result<Vec<TokenTiming>, TranscribeError> {
+    let mut tokens = Vec::new();
+    let mut dtw_times = Vec::new();
+
+    for token in 0..state.full_n_tokens(segment)? {
+        let data = state.full_get_token_data(segment, token)?;
+        if data.id >= token_eot {
+            continue;
         }
 
-        Ok(TranscriptionResult {
-            text: full_text.trim().to_string(),
-            segments,
+        tokens.push(TokenTiming {
+            text: state.full_get_token_text_lossy(segment, token)?,
+            start: data.t0 as f32 / 100.0,
+            end: data.t1 as f32 / 100.0,
+            probability: data.p,
+        });
+        dtw_times.push(data.t_dtw);
+    }
+
+    // whisper.cpp reports -1 when DTW is disabled
+    if dtw_times.iter().all(|&t| t >= 0) {
+        for (i, token) in tokens.iter_mut().enumerate() {
+            let start = dtw_times[i] as f32 / 100.0;
+            let end = dtw_times
+                .get(i + 1)
+                .map_or(segment_end, |&t| t as f32 / 100.0);
+            token.start = start;
+            token.end = end.max(start);
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Group the text tokens of a segment into words.
+///
+/// Whisper marks the first token of each word with a leading space, so a new
+/// word starts at every such token and continuation tokens (sub-words and
+/// punctuation) are appended to the current word. Word probability is the
+/// mean of its token probabilities.
+fn group_words(tokens: &[TokenTiming]) -> Vec<WordTiming> {
+    // (word, sum of token probabilities, token count)
+    let mut words: Vec<(WordTiming, f32, usize)> = Vec::new();
+
+    for token in tokens {
+        match words.last_mut() {
+            Some((word, probability_sum, count)) if !token.text.starts_with(' ') => {
+                word.text.push_str(&token.text);
+                word.end = token.end;
+                *probability_sum += token.probability;
+                *count += 1;
+            }
+            _ => words.push((
+                WordTiming {
+                    text: token.text.trim_start().to_string(),
+                    start: token.start,
+                    end: token.end,
+                    probability: None,
+                },
+                token.probability,
+                1,
+            )),
+        }
+    }
+
+    words
+        .into_iter()
+        .filter(|(word, _, _)| !word.text.trim().is_empty())
+        .map(|(mut word, probability_sum, count)| {
+            word.text = word.text.trim().to_string();
+            word.probability = Some(probability_sum / count as f32);
+            word
         })
+        .collect()
+}
+
+/// Mean probability of `tokens`, or `None` if there are none.
+fn mean_probability(tokens: &[TokenTiming]) -> Option<f32> {
+    if tokens.is_empty() {
+        return None;
     }
+    Some(tokens.iter().map(|token| token.probability).sum::<f32>() / tokens.len() as f32)
 }
@@ -0,0 +1,191 @@
+//! Concurrent Whisper transcription from a pool of states.
+//!
+//! A loaded Whisper model ([`WhisperContext`]) is read-only and can serve any
+//! number of decoding states, each of which holds the buffers for one run.
+//! [`WhisperPool`] loads the model once and creates a fixed number of states
+//! up front, so up to that many transcriptions run in parallel while sharing
+//! the model's memory.
+
+use super::{
+    context_from_bytes, load_context, transcribe_with_state, WhisperInferenceParams,
+    WhisperModelParams,
+};
+use crate::{audio, TranscribeError, TranscriptionResult};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use whisper_rs::{WhisperContext, WhisperState};
+
+/// A Whisper model shared by a fixed number of decoding states.
+///
+/// Unlike [`WhisperEngine`](super::WhisperEngine), transcription takes
+/// `&self`, so a pool can be shared between threads (e.g. behind an
+/// [`Arc`](std::sync::Arc)). Each call borrows an idle state for the duration
+/// of the run and blocks until one is free when all of them are busy.
+///
+/// Calls may run on any state, so [`WhisperInferenceParams::no_context`]
+/// should be left enabled; carrying context over between unrelated calls
+/// has no useful meaning.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use std::sync::Arc;
+/// use std::thread;
+/// use transcribe_rs::engines::whisper::WhisperPool;
+///
+/// let pool = Arc::new(WhisperPool::new(Path::new("models/whisper-medium-q4_1.bin"), 4)?);
+///
+/// let workers: Vec<_> = ["a.wav", "b.wav", "c.wav"]
+///     .into_iter()
+///     .map(|file| {
+///         let pool = Arc::clone(&pool);
+///         thread::spawn(move || pool.transcribe_file(Path::new(file), None))
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     println!("{}", worker.join().unwrap()?.text);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct WhisperPool {
+    model_path: Option<PathBuf>,
+    context: WhisperContext,
+    idle: Mutex<Vec<WhisperState>>,
+    state_returned: Condvar,
+    size: usize,
+}
+
+impl WhisperPool {
+    /// Load a model with default parameters and create `size` states.
+    ///
+    /// At least one state is always created.
+    pub fn new(model_path: &Path, size: usize) -> Result<Self, TranscribeError> {
+        Self::with_params(model_path, WhisperModelParams::default(), size)
+    }
+
+    /// Load a model with custom parameters and create `size` states.
+    ///
+    /// At least one state is always created.
+    pub fn with_params(
+        model_path: &Path,
+        params: WhisperModelParams,
+        size: usize,
+    ) -> Result<Self, TranscribeError> {
+        let context = load_context(model_path, params)?;
+        Self::with_context(context, Some(model_path.to_path_buf()), size)
+    }
+
+    /// Load a model from GGML data held in memory and create `size` states.
+    ///
+    /// See [`WhisperEngine::load_model_from_bytes`](super::WhisperEngine::load_model_from_bytes).
+    /// At least one state is always created.
+    pub fn from_bytes(
+        model_bytes: &[u8],
+        params: WhisperModelParams,
+        size: usize,
+    ) -> Result<Self, TranscribeError> {
+        let context = context_from_bytes(model_bytes, &params)?;
+        Self::with_context(context, None, size)
+    }
+
+    fn with_context(
+        context: WhisperContext,
+        model_path: Option<PathBuf>,
+        size: usize,
+    ) -> Result<Self, TranscribeError> {
+        let size = size.max(1);
+        let idle = (0..size)
+            .map(|_| context.create_state())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            model_path,
+            context,
+            idle: Mutex::new(idle),
+            state_returned: Condvar::new(),
+            size,
+        })
+    }
+
+    /// Path of the loaded model, or `None` if it was loaded from memory.
+    pub fn model_path(&self) -> Option<&Path> {
+        self.model_path.as_deref()
+    }
+
+    /// Number of states, i.e. the maximum number of concurrent transcriptions.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Transcribe 16 kHz mono `samples`, waiting for an idle state if needed.
+    ///
+    /// Behaves like [`WhisperEngine::transcribe_samples`](crate::TranscriptionEngine::transcribe_samples).
+    pub fn transcribe_samples(
+        &self,
+        samples: Vec<f32>,
+        params: Option<WhisperInferenceParams>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let params = params.unwrap_or_default();
+        params.validate()?;
+        let mut state = self.checkout();
+        transcribe_with_state(&self.context, state.state_mut(), &samples, params)
+    }
+
+    /// Transcribe an audio file, waiting for an idle state if needed.
+    ///
+    /// The file is decoded before a state is borrowed, so decoding does not
+    /// hold up other callers.
+    pub fn transcribe_file(
+        &self,
+        audio_path: &Path,
+        params: Option<WhisperInferenceParams>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let samples = audio::read_audio_samples(audio_path)?;
+        self.transcribe_samples(samples, params)
+    }
+
+    /// Take an idle state, blocking until one is returned if all are in use.
+    fn checkout(&self) -> PooledState<'_> {
+        let mut idle = self.lock_idle();
+        loop {
+            if let Some(state) = idle.pop() {
+                return PooledState {
+                    pool: self,
+                    state: Some(state),
+                };
+            }
+            idle = self
+                .state_returned
+                .wait(idle)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn lock_idle(&self) -> MutexGuard<'_, Vec<WhisperState>> {
+        // The list of idle states stays consistent even if a holder panicked.
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A state borrowed from a [`WhisperPool`], returned to it on drop.
+struct PooledState<'a> {
+    pool: &'a WhisperPool,
+    state: Option<WhisperState>,
+}
+
+impl PooledState<'_> {
+    fn state_mut(&mut self) -> &mut WhisperState {
+        self.state.as_mut().expect("state is present until drop")
+    }
+}
+
+impl Drop for PooledState<'_> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            self.pool.lock_idle().push(state);
+            self.pool.state_returned.notify_one();
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Error types for transcription operations.
+//!
+//! Every fallible operation in the crate returns [`TranscribeError`], so
+//! callers can react to specific failure modes (a missing model file, an
+//! unsupported input format, a cancelled run) without matching on strings.
+
+use std::path::PathBuf;
+
+use crate::engines::ModelValidation;
+use crate::TranscriptionTask;
+
+#[cfg(feature = "parakeet")]
+use crate::engines::parakeet::ParakeetError;
+
+/// Errors produced while loading models, reading audio or running inference.
+#[derive(thiserror::Error, Debug)]
+pub enum TranscribeError {
+    /// Transcription was requested before a model was loaded.
+    #[error("Model not loaded. Call load_model() first.")]
+    ModelNotLoaded,
+
+    /// No engine with the requested name is available in this build.
+    #[error("Unknown transcription engine: {0}")]
+    UnknownEngine(String),
+
+    /// A required model file or directory does not exist.
+    #[error("Model file not found: {}", .0.display())]
+    ModelFileMissing(PathBuf),
+
+    /// A model exists but is incomplete or malformed. The report lists every
+    /// problem found.
+    #[error("Invalid model: {0}")]
+    InvalidModel(ModelValidation),
+
+    /// The audio input cannot be used, either because its format is not
+    /// supported or because it does not meet the requested constraints.
+    ///
+    /// `spec` holds the offending WAV specification when one is known.
+    #[error("Unsupported audio format: {reason}")]
+    UnsupportedAudioFormat {
+        /// Human-readable description of the problem
+        reason: String,
+        /// The WAV specification of the rejected input, if available
+        spec: Option<hound::WavSpec>,
+    },
+
+    /// Reading or parsing a WAV file failed.
+    #[error("WAV error: {0}")]
+    Wav(#[from] hound::Error),
+
+    /// Decoding a compressed audio file failed.
+    #[cfg(feature = "codecs")]
+    #[error("Audio decoding error: {0}")]
+    Decode(#[from] symphonia::core::errors::Error),
+
+    /// Sample rate conversion failed.
+    #[error("Resampling error: {0}")]
+    Resample(String),
+
+    /// An I/O operation failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The Whisper engine failed while loading a model or running inference.
+    #[error("Whisper error: {0}")]
+    Whisper(#[from] whisper_rs::WhisperError),
+
+    /// The Parakeet engine failed while loading a model or running inference.
+    #[cfg(feature = "parakeet")]
+    #[error("Parakeet error: {0}")]
+    Parakeet(ParakeetError),
+
+    /// A model or inference parameter has a value the engine cannot honour.
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    /// The engine cannot perform the requested task, e.g. translation with
+    /// an engine that only transcribes.
+    #[error("Task {0:?} is not supported by this engine")]
+    UnsupportedTask(TranscriptionTask),
+
+    /// Chunked transcription cannot drop the words repeated in the overlap
+    /// between chunks, because the chunk at this index has text but no
+    /// segment timestamps.
+    #[error("Chunk {0} has text but no segments, so its overlap cannot be stitched")]
+    UnstitchableChunk(usize),
+
+    /// The operation was cancelled before it completed.
+    #[error("Transcription cancelled")]
+    Cancelled,
+
+    /// An error raised by a transcriber implemented outside this crate.
+    #[error(transparent)]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl TranscribeError {
+    /// Create an [`TranscribeError::UnsupportedAudioFormat`] without a WAV spec.
+    pub fn unsupported_audio(reason: impl Into<String>) -> Self {
+        Self::UnsupportedAudioFormat {
+            reason: reason.into(),
+            spec: None,
+        }
+    }
+}
+
+#[cfg(feature = "parakeet")]
+impl From<ParakeetError> for TranscribeError {
+    fn from(err: ParakeetError) -> Self {
+        match err {
+            ParakeetError::Cancelled => Self::Cancelled,
+            err => Self::Parakeet(err),
+        }
+    }
+}
+
+impl From<rubato::ResampleError> for TranscribeError {
+    fn from(err: rubato::ResampleError) -> Self {
+        Self::Resample(err.to_string())
+    }
+}
+
+impl From<rubato::ResamplerConstructionError> for TranscribeError {
+    fn from(err: rubato::ResamplerConstructionError) -> Self {
+        Self::Resample(err.to_string())
+    }
+}
@@ -8,8 +8,12 @@
 //! - **Multiple Engines**: Support for both Whisper and Parakeet transcription engines
 //! - **Flexible Model Loading**: Load models with custom parameters (quantization, etc.)
 //! - **Timestamped Results**: Get detailed timing information for transcribed segments
-//! - **Audio Processing**: Built-in WAV file processing with proper format validation
-//! - **Unified API**: Common trait-based interface for all transcription engines
+//! - **Audio Processing**: Built-in WAV loading with automatic resampling and downmixing
+//! - **Long Recordings**: Overlapping, silence-aware chunking via [`chunking`]
+//! - **Progress and Cancellation**: Observe long runs and stop them early via [`progress`]
+//! - **Unified API**: Common trait-based interface for all transcription engines, with
+//!   [`DynTranscriber`] for choosing an engine at runtime
+//! - **Serialization**: Results implement `Serialize`/`Deserialize` with the `serde` feature
 //!
 //! ## Model Format Requirements
 //!
@@ -37,34 +41,88 @@
 //! ## Audio Requirements
 //!
 //! Input audio files must be:
-//! - WAV format
-//! - 16 kHz sample rate
-//! - 16-bit samples
-//! - Mono (single channel)
+//! - WAV format, or FLAC, Ogg/Vorbis, MP3 and AAC with the `codecs` feature
+//! - 8, 16, 24 or 32-bit integer PCM, or 32-bit float samples (WAV)
+//!
+//! Any sample rate and channel count is accepted; audio is converted to
+//! 16 kHz mono before inference. Use [`audio::read_wav_samples_with_params`]
+//! with [`audio::AudioReadParams::strict`] to reject files that would need
+//! conversion.
+//!
+//! ## Error Handling
+//!
+//! All fallible operations return [`TranscribeError`], which distinguishes
+//! missing models, unsupported audio, engine failures and cancellation:
+//!
+//! ```rust,no_run
+//! use transcribe_rs::{TranscribeError, TranscriptionEngine, engines::whisper::WhisperEngine};
+//! use std::path::Path;
+//!
+//! let mut engine = WhisperEngine::new();
+//! match engine.load_model(Path::new("models/whisper-medium-q4_1.bin")) {
+//!     Ok(()) => {}
+//!     Err(TranscribeError::ModelFileMissing(path)) => eprintln!("download {}", path.display()),
+//!     Err(err) => return Err(err),
+//! }
+//! # Ok::<(), TranscribeError>(())
+//! ```
 
 pub mod audio;
+pub mod chunking;
 pub mod engines;
+pub mod error;
+pub mod progress;
 pub mod realtime;
 
+pub use error::TranscribeError;
+pub use progress::{CancellationToken, ProgressObserver};
+
+use audio::AudioSource;
 use std::path::Path;
+use std::sync::Arc;
+
+/// Maximum length of the chunks, in seconds, that
+/// [`TranscriptionEngine::transcribe_source`] transcribes at a time.
+pub const SOURCE_WINDOW_SECONDS: f32 = 30.0;
 
 /// The result of a transcription operation.
 ///
 /// Contains both the full transcribed text and detailed timing information
 /// for individual segments within the audio.
-#[derive(Debug)]
+///
+/// With the `serde` feature enabled, results serialize to the JSON shape
+/// described by `docs/transcription-result.schema.json`:
+///
+/// ```json
+/// {"text": "hello world", "segments": [{"start": 0.0, "end": 1.5, "text": "hello world"}]}
+/// ```
+///
+/// Fields added in future versions are optional in that schema, so cached
+/// results remain readable across upgrades.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TranscriptionResult {
     /// The complete transcribed text from the audio
     pub text: String,
     /// Individual segments with timing information
     pub segments: Vec<TranscriptionSegment>,
+    /// Language code of the speech (e.g., "en"), when the engine reports it.
+    /// Whisper fills this in with the detected or requested language.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub language: Option<String>,
 }
 
 /// A single transcribed segment with timing information.
 ///
 /// Represents a portion of the transcribed audio with start and end timestamps
-/// and the corresponding text content.
-#[derive(Debug)]
+/// and the corresponding text content. Engines that can time individual words
+/// also fill in [`TranscriptionSegment::words`], so a single result can drive
+/// both subtitle lines and word highlighting.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TranscriptionSegment {
     /// Start time of the segment in seconds
     pub start: f32,
@@ -72,6 +130,118 @@ pub struct TranscriptionSegment {
     pub end: f32,
     /// The transcribed text for this segment
     pub text: String,
+    /// Word-level timing within the segment; empty when the engine or the
+    /// requested options do not provide it
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub words: Vec<WordTiming>,
+    /// Confidence of the engine in this segment's text, from 0.0 to 1.0, if
+    /// the engine reports one
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub probability: Option<f32>,
+}
+
+impl TranscriptionSegment {
+    /// Shift the segment and all of its words later by `offset` seconds.
+    ///
+    /// Useful when stitching together results from consecutive audio windows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use transcribe_rs::TranscriptionSegment;
+    ///
+    /// let segment = TranscriptionSegment {
+    ///     start: 1.0,
+    ///     end: 2.0,
+    ///     text: "hello".to_string(),
+    ///     words: Vec::new(),
+    ///     probability: None,
+    /// };
+    /// assert_eq!(segment.offset_by(30.0).start, 31.0);
+    /// ```
+    pub fn offset_by(mut self, offset: f32) -> Self {
+        self.start += offset;
+        self.end += offset;
+        for word in &mut self.words {
+            word.start += offset;
+            word.end += offset;
+        }
+        self
+    }
+}
+
+/// Timing and confidence for a single word within a [`TranscriptionSegment`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordTiming {
+    /// The word text, without surrounding whitespace
+    pub text: String,
+    /// Start time of the word in seconds
+    pub start: f32,
+    /// End time of the word in seconds
+    pub end: f32,
+    /// Confidence of the word in the range [0.0, 1.0], if the engine reports one
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub probability: Option<f32>,
+}
+
+/// Granularity level for timestamp generation.
+///
+/// Controls the level of detail in the timing information returned
+/// by an engine. Engines that cannot time individual tokens fall back
+/// to the closest level they support.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TimestampGranularity {
+    /// Token-level timestamps (most detailed, default)
+    #[default]
+    Token,
+    /// Word-level timestamps (grouped tokens into words)
+    Word,
+    /// Segment-level timestamps (larger phrases/sentences)
+    Segment,
+}
+
+/// What to produce from the input speech.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscriptionTask {
+    /// Transcribe speech in the language it is spoken (default)
+    #[default]
+    Transcribe,
+    /// Translate speech into English text
+    Translate,
+}
+
+/// Engine-independent inference options used by [`DynTranscriber`].
+///
+/// Each engine maps these onto its own inference parameters and ignores
+/// options it has no equivalent for.
+#[derive(Debug, Clone, Default)]
+pub struct TranscribeOptions {
+    /// Target language code (e.g., "en"). `None` lets the engine decide.
+    pub language: Option<String>,
+    /// The granularity level for timestamp generation. `None` uses the
+    /// engine default: phrase-level segments for Whisper, tokens for Parakeet.
+    pub timestamp_granularity: Option<TimestampGranularity>,
+    /// Whether to transcribe or translate to English. Only Whisper supports
+    /// translation; other engines fail with
+    /// [`TranscribeError::UnsupportedTask`].
+    pub task: TranscriptionTask,
+    /// Number of CPU threads used for inference, if the engine supports
+    /// setting it per call. `None` uses the engine default.
+    pub n_threads: Option<usize>,
+    /// Observer notified of progress and finalized segments
+    pub progress: Option<Arc<dyn ProgressObserver>>,
+    /// Token that stops the run with [`TranscribeError::Cancelled`] when cancelled
+    pub cancellation: Option<CancellationToken>,
 }
 
 /// Common interface for speech transcription engines.
@@ -128,8 +298,8 @@ pub trait TranscriptionEngine {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the model loads successfully, or an error if loading fails.
-    fn load_model(&mut self, model_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    /// Returns `Ok(())` if the model loads successfully, or a [`TranscribeError`] if loading fails.
+    fn load_model(&mut self, model_path: &Path) -> Result<(), TranscribeError> {
         self.load_model_with_params(model_path, Self::ModelParams::default())
     }
 
@@ -142,16 +312,25 @@ pub trait TranscriptionEngine {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the model loads successfully, or an error if loading fails.
+    /// Returns `Ok(())` if the model loads successfully, or a [`TranscribeError`] if loading fails.
     fn load_model_with_params(
         &mut self,
         model_path: &Path,
         params: Self::ModelParams,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<(), TranscribeError>;
 
     /// Unload the currently loaded model and free associated resources.
     fn unload_model(&mut self);
 
+    /// Whether the engine can perform `task`.
+    ///
+    /// The default only accepts [`TranscriptionTask::Transcribe`]; engines
+    /// that can translate override it. [`DynTranscriber`] rejects options
+    /// asking for any other task with [`TranscribeError::UnsupportedTask`].
+    fn supports_task(&self, task: TranscriptionTask) -> bool {
+        task == TranscriptionTask::Transcribe
+    }
+
     /// Transcribe audio samples directly.
     ///
     /// # Arguments
@@ -166,19 +345,18 @@ pub trait TranscriptionEngine {
         &mut self,
         samples: Vec<f32>,
         params: Option<Self::InferenceParams>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>>;
+    ) -> Result<TranscriptionResult, TranscribeError>;
 
-    /// Transcribe audio from a WAV file.
+    /// Transcribe audio from a file.
     ///
-    /// The WAV file must meet the following requirements:
-    /// - 16 kHz sample rate
-    /// - 16-bit samples
-    /// - Mono (single channel)
-    /// - PCM format
+    /// WAV files may contain integer PCM (8, 16, 24 or 32-bit) or 32-bit
+    /// float samples. With the `codecs` feature, FLAC, Ogg/Vorbis, MP3 and
+    /// AAC files are decoded as well. Audio with other sample rates or
+    /// multiple channels is resampled to 16 kHz and downmixed to mono.
     ///
     /// # Arguments
     ///
-    /// * `wav_path` - Path to the WAV file to transcribe
+    /// * `audio_path` - Path to the audio file to transcribe
     /// * `params` - Optional engine-specific inference parameters
     ///
     /// # Returns
@@ -186,10 +364,180 @@ pub trait TranscriptionEngine {
     /// Returns transcription result with text and timing information.
     fn transcribe_file(
         &mut self,
-        wav_path: &Path,
+        audio_path: &Path,
         params: Option<Self::InferenceParams>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
-        let samples = audio::read_wav_samples(wav_path)?;
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let samples = audio::read_audio_samples(audio_path)?;
         self.transcribe_samples(samples, params)
     }
+
+    /// Transcribe audio pulled incrementally from an [`AudioSource`].
+    ///
+    /// Frames are split into overlapping chunks of up to
+    /// [`SOURCE_WINDOW_SECONDS`], cut at quiet points, and each chunk is
+    /// transcribed as soon as it is complete, so memory use stays bounded
+    /// regardless of the length of the recording. The chunk results are
+    /// stitched as described in [`chunking`], so words spoken across a chunk
+    /// boundary appear exactly once, with timestamps relative to the start of
+    /// the source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Source yielding 16 kHz mono frames
+    /// * `params` - Optional engine-specific inference parameters, applied to every window
+    ///
+    /// # Returns
+    ///
+    /// Returns the combined transcription result for the whole source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use transcribe_rs::{TranscriptionEngine, engines::whisper::WhisperEngine};
+    /// use transcribe_rs::audio::{WavFileSource, DEFAULT_FRAME_SIZE};
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let mut engine = WhisperEngine::new();
+    /// engine.load_model(&PathBuf::from("models/whisper-medium-q4_1.bin"))?;
+    ///
+    /// let mut source = WavFileSource::open(Path::new("all-hands.wav"), DEFAULT_FRAME_SIZE)?;
+    /// let result = engine.transcribe_source(&mut source, None)?;
+    /// println!("Transcription: {}", result.text);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn transcribe_source(
+        &mut self,
+        source: &mut dyn AudioSource,
+        params: Option<Self::InferenceParams>,
+    ) -> Result<TranscriptionResult, TranscribeError>
+    where
+        Self::InferenceParams: Clone,
+    {
+        let chunking = chunking::ChunkingParams::default();
+        chunking::transcribe_source_chunked_with(source, &chunking, |chunk| {
+            self.transcribe_samples(chunk, params.clone())
+        })
+    }
+}
+
+/// Object-safe counterpart of [`TranscriptionEngine`].
+///
+/// [`TranscriptionEngine`] uses associated parameter types and therefore
+/// cannot be used as a trait object. `DynTranscriber` takes the common
+/// [`TranscribeOptions`] instead, so engines chosen at runtime can be stored
+/// as `Box<dyn DynTranscriber>`. It is implemented for every
+/// [`TranscriptionEngine`] whose inference parameters can be built from
+/// [`TranscribeOptions`]; see [`engines::create_engine`] for constructing
+/// one by name.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use transcribe_rs::{DynTranscriber, TranscribeOptions, engines::create_engine};
+/// use std::path::Path;
+///
+/// let mut engine: Box<dyn DynTranscriber> =
+///     create_engine("whisper", Path::new("models/whisper-medium-q4_1.bin"))?;
+///
+/// let options = TranscribeOptions {
+///     language: Some("en".to_string()),
+///     ..Default::default()
+/// };
+/// let result = engine.transcribe_file(Path::new("audio.wav"), &options)?;
+/// println!("Transcription: {}", result.text);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait DynTranscriber: Send {
+    /// Load a model from the specified path using default parameters.
+    fn load_model(&mut self, model_path: &Path) -> Result<(), TranscribeError>;
+
+    /// Unload the currently loaded model and free associated resources.
+    fn unload_model(&mut self);
+
+    /// Whether the engine can perform `task`, see
+    /// [`TranscriptionEngine::supports_task`].
+    fn supports_task(&self, task: TranscriptionTask) -> bool;
+
+    /// Transcribe 16 kHz mono audio samples directly.
+    ///
+    /// Fails with [`TranscribeError::UnsupportedTask`] if the engine cannot
+    /// perform `options.task`.
+    fn transcribe_samples(
+        &mut self,
+        samples: Vec<f32>,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError>;
+
+    /// Transcribe audio from a file, see [`TranscriptionEngine::transcribe_file`].
+    fn transcribe_file(
+        &mut self,
+        audio_path: &Path,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError>;
+
+    /// Transcribe audio pulled incrementally from an [`AudioSource`], see
+    /// [`TranscriptionEngine::transcribe_source`].
+    fn transcribe_source(
+        &mut self,
+        source: &mut dyn AudioSource,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError>;
+}
+
+impl<E> DynTranscriber for E
+where
+    E: TranscriptionEngine + Send,
+    E::InferenceParams: for<'a> From<&'a TranscribeOptions> + Clone,
+{
+    fn load_model(&mut self, model_path: &Path) -> Result<(), TranscribeError> {
+        TranscriptionEngine::load_model(self, model_path)
+    }
+
+    fn unload_model(&mut self) {
+        TranscriptionEngine::unload_model(self)
+    }
+
+    fn supports_task(&self, task: TranscriptionTask) -> bool {
+        TranscriptionEngine::supports_task(self, task)
+    }
+
+    fn transcribe_samples(
+        &mut self,
+        samples: Vec<f32>,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        check_task(self, options)?;
+        TranscriptionEngine::transcribe_samples(self, samples, Some(options.into()))
+    }
+
+    fn transcribe_file(
+        &mut self,
+        audio_path: &Path,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        check_task(self, options)?;
+        TranscriptionEngine::transcribe_file(self, audio_path, Some(options.into()))
+    }
+
+    fn transcribe_source(
+        &mut self,
+        source: &mut dyn AudioSource,
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        check_task(self, options)?;
+        TranscriptionEngine::transcribe_source(self, source, Some(options.into()))
+    }
+}
+
+/// Reject options asking `engine` for a task it cannot perform, rather than
+/// silently doing something else.
+fn check_task<E: TranscriptionEngine>(
+    engine: &E,
+    options: &TranscribeOptions,
+) -> Result<(), TranscribeError> {
+    if engine.supports_task(options.task) {
+        Ok(())
+    } else {
+        Err(TranscribeError::UnsupportedTask(options.task))
+    }
 }
@@ -0,0 +1,103 @@
+//! Progress reporting and cancellation for long transcriptions.
+//!
+//! Both engines accept an optional [`ProgressObserver`] and
+//! [`CancellationToken`] through their inference parameters (and through
+//! [`TranscribeOptions`](crate::TranscribeOptions)). The observer is told how
+//! far the run has progressed and receives segments as they are finalized;
+//! the token can be cancelled from another thread to stop the run early, in
+//! which case the engine returns [`TranscribeError::Cancelled`](crate::TranscribeError::Cancelled).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use transcribe_rs::{CancellationToken, ProgressObserver, TranscriptionEngine, TranscriptionSegment};
+//! use transcribe_rs::engines::whisper::{WhisperEngine, WhisperInferenceParams};
+//! use std::path::Path;
+//!
+//! struct Printer;
+//!
+//! impl ProgressObserver for Printer {
+//!     fn on_progress(&self, percent: f32) {
+//!         eprintln!("{percent:.0}%");
+//!     }
+//!
+//!     fn on_segment(&self, segment: &TranscriptionSegment) {
+//!         println!("[{:.2}s] {}", segment.start, segment.text);
+//!     }
+//! }
+//!
+//! let mut engine = WhisperEngine::new();
+//! engine.load_model(Path::new("models/whisper-medium-q4_1.bin"))?;
+//!
+//! let cancellation = CancellationToken::new();
+//! let params = WhisperInferenceParams {
+//!     progress: Some(Arc::new(Printer)),
+//!     cancellation: Some(cancellation.clone()),
+//!     ..Default::default()
+//! };
+//!
+//! // Call `cancellation.cancel()` from another thread to stop early.
+//! let result = engine.transcribe_file(Path::new("audio.wav"), Some(params))?;
+//! # let _ = result;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::TranscriptionSegment;
+
+/// Receives progress updates from a running transcription.
+///
+/// Callbacks are invoked on the thread running the transcription, possibly
+/// from inside the inference library, so they should return quickly. A panic
+/// in a callback stops the run and is raised again once the engine returns.
+pub trait ProgressObserver: Send + Sync {
+    /// Called as the run advances with the percentage completed, from 0.0 to 100.0.
+    fn on_progress(&self, _percent: f32) {}
+
+    /// Called for each segment as soon as the engine has finalized it.
+    ///
+    /// Timestamps are relative to the start of the samples being transcribed.
+    /// Word timings are not included; they are available in the final result.
+    fn on_segment(&self, _segment: &TranscriptionSegment) {}
+}
+
+impl fmt::Debug for dyn ProgressObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressObserver")
+    }
+}
+
+/// A cheaply cloneable flag used to request that a transcription stop.
+///
+/// All clones share the same state, so a token handed to an engine can be
+/// cancelled from any other thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every run observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called on this token or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// The shared flag, for passing to C callbacks.
+    pub(crate) fn flag(&self) -> &AtomicBool {
+        &self.cancelled
+    }
+}
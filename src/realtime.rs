@@ -1,11 +1,14 @@
-use serde::{Deserialize, Serialize};
-use std::error::Error;
-
-use crate::{TranscriptionResult, TranscriptionSegment};
+use crate::{
+    DynTranscriber, TranscribeError, TranscribeOptions, TranscriptionResult, TranscriptionSegment,
+};
 
 /// Message format accepted by the realtime CLI helper.
-#[derive(Debug, Deserialize, PartialEq)]
-#[serde(tag = "type", rename_all = "snake_case")]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 pub enum InboundMessage {
     /// Append a new chunk of audio samples to the active session buffer.
     Chunk { samples: Vec<f32> },
@@ -15,27 +18,13 @@ pub enum InboundMessage {
     Flush,
 }
 
-/// Serializable transcript segment forwarded to the renderer.
-#[derive(Debug, Serialize, Clone, PartialEq)]
-pub struct SerializableSegment {
-    pub start: f32,
-    pub end: f32,
-    pub text: String,
-}
-
-impl From<&TranscriptionSegment> for SerializableSegment {
-    fn from(value: &TranscriptionSegment) -> Self {
-        Self {
-            start: value.start,
-            end: value.end,
-            text: value.text.clone(),
-        }
-    }
-}
-
 /// Outbound message format produced by the realtime session.
-#[derive(Debug, Serialize, Clone, PartialEq)]
-#[serde(tag = "type", rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 pub enum OutboundMessage {
     Ready {
         engine: String,
@@ -45,7 +34,9 @@ pub enum OutboundMessage {
     },
     Transcript {
         text: String,
-        segments: Vec<SerializableSegment>,
+        segments: Vec<TranscriptionSegment>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        language: Option<String>,
     },
     Error {
         message: String,
@@ -59,7 +50,58 @@ pub trait RealtimeTranscriber {
         &mut self,
         samples: Vec<f32>,
         language: Option<&str>,
-    ) -> Result<TranscriptionResult, Box<dyn Error>>;
+    ) -> Result<TranscriptionResult, TranscribeError>;
+}
+
+impl RealtimeTranscriber for Box<dyn DynTranscriber> {
+    fn transcribe(
+        &mut self,
+        samples: Vec<f32>,
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let options = TranscribeOptions {
+            language: language.map(str::to_string),
+            ..Default::default()
+        };
+        self.transcribe_samples(samples, &options)
+    }
+}
+
+/// A boxed engine paired with the [`TranscribeOptions`] used for every call.
+///
+/// The session's language hint, when set, takes precedence over
+/// `options.language`.
+pub struct ConfiguredTranscriber {
+    /// The engine used to transcribe
+    pub engine: Box<dyn DynTranscriber>,
+    /// Options applied to every transcription
+    pub options: TranscribeOptions,
+}
+
+impl ConfiguredTranscriber {
+    /// Pair `engine` with `options`.
+    pub fn new(engine: Box<dyn DynTranscriber>, options: TranscribeOptions) -> Self {
+        Self { engine, options }
+    }
+}
+
+impl RealtimeTranscriber for ConfiguredTranscriber {
+    fn transcribe(
+        &mut self,
+        samples: Vec<f32>,
+        language: Option<&str>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        match language {
+            Some(language) => {
+                let options = TranscribeOptions {
+                    language: Some(language.to_string()),
+                    ..self.options.clone()
+                };
+                self.engine.transcribe_samples(samples, &options)
+            }
+            None => self.engine.transcribe_samples(samples, &self.options),
+        }
+    }
 }
 
 /// Stateful helper that aggregates audio chunks and produces outbound updates.
@@ -68,7 +110,8 @@ pub struct RealtimeSession<T: RealtimeTranscriber> {
     language: Option<String>,
     samples: Vec<f32>,
     last_text: String,
-    last_segments: Vec<SerializableSegment>,
+    last_segments: Vec<TranscriptionSegment>,
+    last_language: Option<String>,
 }
 
 impl<T: RealtimeTranscriber> RealtimeSession<T> {
@@ -80,6 +123,7 @@ impl<T: RealtimeTranscriber> RealtimeSession<T> {
             samples: Vec::new(),
             last_text: String::new(),
             last_segments: Vec::new(),
+            last_language: None,
         }
     }
 
@@ -87,7 +131,7 @@ impl<T: RealtimeTranscriber> RealtimeSession<T> {
     pub fn handle_inbound(
         &mut self,
         message: InboundMessage,
-    ) -> Result<Vec<OutboundMessage>, Box<dyn Error>> {
+    ) -> Result<Vec<OutboundMessage>, TranscribeError> {
         match message {
             InboundMessage::Chunk { samples } => {
                 if samples.is_empty() {
@@ -100,18 +144,14 @@ impl<T: RealtimeTranscriber> RealtimeSession<T> {
                     .transcribe(self.samples.clone(), language.as_deref())
                 {
                     Ok(result) => {
-                        let segments: Vec<SerializableSegment> = result
-                            .segments
-                            .iter()
-                            .map(SerializableSegment::from)
-                            .collect();
-
-                        if result.text != self.last_text || segments != self.last_segments {
+                        if result.text != self.last_text || result.segments != self.last_segments {
                             self.last_text = result.text.clone();
-                            self.last_segments = segments.clone();
+                            self.last_segments = result.segments.clone();
+                            self.last_language = result.language.clone();
                             Ok(vec![OutboundMessage::Transcript {
                                 text: result.text,
-                                segments,
+                                segments: result.segments,
+                                language: result.language,
                             }])
                         } else {
                             Ok(Vec::new())
@@ -126,6 +166,7 @@ impl<T: RealtimeTranscriber> RealtimeSession<T> {
                 self.samples.clear();
                 self.last_text.clear();
                 self.last_segments.clear();
+                self.last_language = None;
                 Ok(vec![OutboundMessage::Status {
                     message: "session_reset".to_string(),
                 }])
@@ -137,6 +178,7 @@ impl<T: RealtimeTranscriber> RealtimeSession<T> {
                     Ok(vec![OutboundMessage::Transcript {
                         text: self.last_text.clone(),
                         segments: self.last_segments.clone(),
+                        language: self.last_language.clone(),
                     }])
                 }
             }
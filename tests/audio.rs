@@ -1,6 +1,12 @@
 use std::error::Error;
 
-use transcribe_rs::audio::read_wav_samples;
+use transcribe_rs::TranscribeError;
+
+use transcribe_rs::audio::{
+    detect_container, read_audio_samples, read_audio_samples_with_params, read_wav_samples,
+    read_wav_samples_with_params, AudioContainer, AudioReadParams, AudioSource, BufferSource,
+    PcmEncoding, PcmFormat, PcmReaderSource, WavFileSource,
+};
 
 #[test]
 fn read_wav_samples_normalizes_full_range() -> Result<(), Box<dyn Error>> {
@@ -29,3 +35,397 @@ fn read_wav_samples_normalizes_full_range() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+fn write_wav(
+    path: &std::path::Path,
+    channels: u16,
+    sample_rate: u32,
+    samples: &[i16],
+) -> Result<(), Box<dyn Error>> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_resamples_and_downmixes_stereo() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("stereo_48k.wav");
+
+    // One second of a 440 Hz tone in the left channel, silence in the right
+    let frames: Vec<i16> = (0..48_000)
+        .flat_map(|i| {
+            let t = i as f32 / 48_000.0;
+            let left = ((t * 440.0 * std::f32::consts::TAU).sin() * 16_000.0) as i16;
+            [left, 0]
+        })
+        .collect();
+    write_wav(&wav_path, 2, 48_000, &frames)?;
+
+    let samples = read_wav_samples(&wav_path)?;
+    assert_eq!(samples.len(), 16_000);
+
+    // Downmixing halves the amplitude of a single-channel tone
+    let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+    let expected_peak = 16_000.0 / i16::MAX as f32 / 2.0;
+    assert!(
+        (peak - expected_peak).abs() < 0.05,
+        "unexpected peak {peak}, expected about {expected_peak}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_resamples_fractional_rates() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("mono_44k.wav");
+
+    write_wav(&wav_path, 1, 44_100, &vec![1000; 22_050])?;
+
+    let samples = read_wav_samples(&wav_path)?;
+    assert_eq!(samples.len(), 8_000);
+
+    Ok(())
+}
+
+#[test]
+fn strict_mode_rejects_non_engine_format() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let stereo_path = temp_dir.path().join("stereo.wav");
+    let rate_path = temp_dir.path().join("rate.wav");
+
+    write_wav(&stereo_path, 2, 16_000, &[0, 0, 0, 0])?;
+    write_wav(&rate_path, 1, 44_100, &[0, 0])?;
+
+    let err = read_wav_samples_with_params(&stereo_path, AudioReadParams::strict())
+        .expect_err("strict mode should reject stereo input");
+    assert!(err.to_string().contains("channels"));
+    match err {
+        TranscribeError::UnsupportedAudioFormat {
+            spec: Some(spec), ..
+        } => assert_eq!(spec.channels, 2),
+        other => panic!("expected unsupported format error, got {other:?}"),
+    }
+
+    let err = read_wav_samples_with_params(&rate_path, AudioReadParams::strict())
+        .expect_err("strict mode should reject 44.1 kHz input");
+    assert!(err.to_string().contains("sample rate"));
+
+    Ok(())
+}
+
+fn write_int_wav(path: &std::path::Path, bits: u16, samples: &[i32]) -> Result<(), Box<dyn Error>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: bits,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_decodes_8_bit_int() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("int8.wav");
+    write_int_wav(&wav_path, 8, &[127, -128, 0, 64])?;
+
+    let samples = read_wav_samples(&wav_path)?;
+    assert_eq!(samples, vec![1.0, -1.0, 0.0, 64.0 / 127.0]);
+
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_decodes_24_bit_int() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("int24.wav");
+    let max = (1 << 23) - 1;
+    write_int_wav(&wav_path, 24, &[max, -(1 << 23), 0, max / 2])?;
+
+    let samples = read_wav_samples(&wav_path)?;
+    assert_eq!(samples.len(), 4);
+    assert_eq!(samples[0], 1.0);
+    assert_eq!(samples[1], -1.0);
+    assert_eq!(samples[2], 0.0);
+    assert!((samples[3] - 0.5).abs() < 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_decodes_32_bit_int() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("int32.wav");
+    write_int_wav(&wav_path, 32, &[i32::MAX, i32::MIN, 0, i32::MAX / 4])?;
+
+    let samples = read_wav_samples(&wav_path)?;
+    assert_eq!(samples.len(), 4);
+    assert_eq!(samples[0], 1.0);
+    assert_eq!(samples[1], -1.0);
+    assert_eq!(samples[2], 0.0);
+    assert!((samples[3] - 0.25).abs() < 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn read_wav_samples_decodes_32_bit_float() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("float32.wav");
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+
+    {
+        let mut writer = hound::WavWriter::create(&wav_path, spec)?;
+        for sample in [0.5f32, -0.25, 1.5, -2.0] {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()?;
+    }
+
+    let samples = read_wav_samples(&wav_path)?;
+    // Out-of-range float samples are clamped to the normalized range
+    assert_eq!(samples, vec![0.5, -0.25, 1.0, -1.0]);
+
+    Ok(())
+}
+
+#[test]
+fn strict_mode_rejects_non_16_bit_input() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("int24_strict.wav");
+    write_int_wav(&wav_path, 24, &[0, 0])?;
+
+    let err = read_wav_samples_with_params(&wav_path, AudioReadParams::strict())
+        .expect_err("strict mode should reject 24-bit input");
+    assert!(err.to_string().contains("bits per sample"));
+
+    Ok(())
+}
+
+#[test]
+fn detect_container_recognises_common_formats() {
+    assert_eq!(
+        detect_container(b"RIFF\x24\0\0\0WAVEfmt "),
+        AudioContainer::Wav
+    );
+    assert_eq!(detect_container(b"fLaC\0\0\0\x22"), AudioContainer::Flac);
+    assert_eq!(detect_container(b"OggS\0\x02\0\0"), AudioContainer::Ogg);
+    assert_eq!(detect_container(b"ID3\x04\0\0\0\0"), AudioContainer::Mp3);
+    assert_eq!(
+        detect_container(&[0xFF, 0xFB, 0x90, 0x64]),
+        AudioContainer::Mp3
+    );
+    assert_eq!(detect_container(b"\0\0\0\x20ftypM4A "), AudioContainer::Mp4);
+    assert_eq!(detect_container(b"not audio"), AudioContainer::Unknown);
+    assert_eq!(detect_container(b""), AudioContainer::Unknown);
+}
+
+#[test]
+fn read_audio_samples_routes_wav_through_wav_loader() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    // The extension is deliberately misleading: detection uses the header
+    let wav_path = temp_dir.path().join("recording.mp3");
+    write_wav(&wav_path, 1, 16_000, &[i16::MAX, 0])?;
+
+    let samples = read_audio_samples(&wav_path)?;
+    assert_eq!(samples, vec![1.0, 0.0]);
+
+    Ok(())
+}
+
+#[test]
+fn read_audio_samples_rejects_unknown_data() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("garbage.bin");
+    std::fs::write(&path, b"definitely not an audio file")?;
+
+    assert!(read_audio_samples(&path).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn strict_mode_rejects_compressed_containers() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("audio.flac");
+    std::fs::write(&path, b"fLaC\0\0\0\x22")?;
+
+    let err = read_audio_samples_with_params(&path, AudioReadParams::strict())
+        .expect_err("strict mode should only accept WAV");
+    assert!(err.to_string().contains("Flac"));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "codecs"))]
+#[test]
+fn compressed_audio_requires_codecs_feature() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("audio.flac");
+    std::fs::write(&path, b"fLaC\0\0\0\x22")?;
+
+    let err = read_audio_samples(&path).expect_err("FLAC needs the codecs feature");
+    assert!(err.to_string().contains("codecs"));
+
+    Ok(())
+}
+
+/// Small encoded files in `tests/fixtures`: a stereo 22.05 kHz FLAC tone and
+/// silent mono MP3 (44.1 kHz), Ogg/Vorbis (8 kHz) and M4A/AAC (16 kHz) clips.
+#[cfg(feature = "codecs")]
+fn fixture(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+#[cfg(feature = "codecs")]
+#[test]
+fn flac_is_downmixed_and_resampled() -> Result<(), Box<dyn Error>> {
+    // 4410 stereo frames at 22.05 kHz, i.e. 0.2 s
+    let samples = read_audio_samples(&fixture("stereo_22050.flac"))?;
+    assert_eq!(samples.len(), 3200);
+
+    // Each channel peaks at about 0.24, so their average cannot exceed that
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    assert!(peak > 0.1 && peak < 0.25, "unexpected peak {peak}");
+
+    Ok(())
+}
+
+#[cfg(feature = "codecs")]
+#[test]
+fn lossy_formats_decode_to_16khz() -> Result<(), Box<dyn Error>> {
+    // (file, decoded frames at the source rate, source rate)
+    let cases = [
+        ("mono_44100.mp3", 11_520, 44_100),
+        ("mono_8000.ogg", 4_096, 8_000),
+        ("mono_16000.m4a", 20_480, 16_000),
+    ];
+
+    for (name, frames, rate) in cases {
+        let samples = read_audio_samples(&fixture(name))?;
+        let expected = (frames as u64 * 16_000).div_ceil(rate) as usize;
+        assert_eq!(samples.len(), expected, "{name}");
+        assert!(
+            samples.iter().all(|s| s.abs() < 1e-3),
+            "{name} should be silent"
+        );
+    }
+
+    Ok(())
+}
+
+fn collect_frames(source: &mut dyn AudioSource) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let mut frames = Vec::new();
+    while let Some(frame) = source.next_frame()? {
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+#[test]
+fn buffer_source_yields_fixed_size_frames() -> Result<(), Box<dyn Error>> {
+    let samples: Vec<f32> = (0..10).map(|i| i as f32).collect();
+    let mut source = BufferSource::new(samples.clone(), 4);
+
+    let frames = collect_frames(&mut source)?;
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0], samples[..4]);
+    assert_eq!(frames[2], samples[8..]);
+    assert!(source.next_frame()?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn wav_file_source_matches_whole_file_loader() -> Result<(), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let wav_path = temp_dir.path().join("stream_48k.wav");
+
+    let frames: Vec<i16> = (0..96_000)
+        .flat_map(|i| {
+            let t = i as f32 / 48_000.0;
+            let value = ((t * 300.0 * std::f32::consts::TAU).sin() * 12_000.0) as i16;
+            [value, value / 2]
+        })
+        .collect();
+    write_wav(&wav_path, 2, 48_000, &frames)?;
+
+    let expected = read_wav_samples(&wav_path)?;
+    let mut source = WavFileSource::open(&wav_path, 1_000)?;
+    let streamed = collect_frames(&mut source)?;
+
+    assert!(streamed[..streamed.len() - 1]
+        .iter()
+        .all(|frame| frame.len() == 1_000));
+
+    let streamed: Vec<f32> = streamed.into_iter().flatten().collect();
+    assert_eq!(streamed.len(), expected.len());
+    for (a, b) in streamed.iter().zip(&expected) {
+        assert!((a - b).abs() < 1e-4, "streamed {a} differs from {b}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn pcm_reader_source_converts_float_stereo() -> Result<(), Box<dyn Error>> {
+    // Half a second of 32 kHz stereo float PCM
+    let bytes: Vec<u8> = (0..16_000)
+        .flat_map(|_| [0.5f32, -0.5f32])
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect();
+    let format = PcmFormat {
+        sample_rate: 32_000,
+        channels: 2,
+        encoding: PcmEncoding::F32Le,
+    };
+
+    let mut source = PcmReaderSource::new(bytes.as_slice(), format, 1_600)?;
+    let samples: Vec<f32> = collect_frames(&mut source)?.into_iter().flatten().collect();
+
+    assert_eq!(samples.len(), 8_000);
+    assert!(samples.iter().all(|s| s.abs() < 1e-3));
+
+    Ok(())
+}
+
+#[test]
+fn pcm_reader_source_passes_through_engine_format() -> Result<(), Box<dyn Error>> {
+    let values = [i16::MAX, i16::MIN, 0];
+    let mut bytes: Vec<u8> = values.iter().flat_map(|s| s.to_le_bytes()).collect();
+    // A dangling half sample at the end of the stream is ignored
+    bytes.push(0x7f);
+
+    let mut source = PcmReaderSource::new(bytes.as_slice(), PcmFormat::default(), 2)?;
+    let frames = collect_frames(&mut source)?;
+
+    assert_eq!(frames, vec![vec![1.0, -1.0], vec![0.0]]);
+
+    Ok(())
+}
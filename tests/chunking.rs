@@ -0,0 +1,208 @@
+use transcribe_rs::{
+    chunking::{plan_chunks, stitch_results, transcribe_chunked_with, AudioChunk, ChunkingParams},
+    TranscribeError, TranscriptionResult, TranscriptionSegment,
+};
+
+const RATE: usize = 16_000;
+
+fn segment(start: f32, end: f32, text: &str) -> TranscriptionSegment {
+    TranscriptionSegment {
+        start,
+        end,
+        text: text.to_string(),
+        words: Vec::new(),
+        probability: None,
+    }
+}
+
+fn result(segments: Vec<TranscriptionSegment>) -> TranscriptionResult {
+    TranscriptionResult {
+        text: segments.iter().map(|s| s.text.as_str()).collect(),
+        segments,
+        language: None,
+    }
+}
+
+#[test]
+fn short_audio_is_a_single_chunk() {
+    let samples = vec![0.3; RATE * 10];
+    let chunks = plan_chunks(&samples, &ChunkingParams::default());
+    assert_eq!(
+        chunks,
+        vec![AudioChunk {
+            start: 0,
+            end: samples.len()
+        }]
+    );
+}
+
+#[test]
+fn chunks_cover_the_input_with_overlap() {
+    let samples = vec![0.3; RATE * 100];
+    let params = ChunkingParams {
+        chunk_seconds: 30.0,
+        overlap_seconds: 2.0,
+        silence_search_seconds: 5.0,
+    };
+
+    let chunks = plan_chunks(&samples, &params);
+
+    // Uniform audio has no quieter point, so every cut lands on the nominal end.
+    assert_eq!(
+        chunks[0],
+        AudioChunk {
+            start: 0,
+            end: RATE * 30
+        }
+    );
+    assert_eq!(chunks[1].start, RATE * 28);
+    assert_eq!(chunks.last().unwrap().end, samples.len());
+    for pair in chunks.windows(2) {
+        assert_eq!(pair[0].end - pair[1].start, RATE * 2);
+        assert!(pair[1].start > pair[0].start);
+    }
+}
+
+#[test]
+fn chunks_are_cut_in_silence() {
+    let mut samples = vec![0.3; RATE * 60];
+    let gap = RATE * 27..RATE * 27 + RATE / 5;
+    samples[gap.clone()].fill(0.0);
+
+    let chunks = plan_chunks(&samples, &ChunkingParams::default());
+
+    assert!(
+        gap.contains(&chunks[0].end),
+        "expected cut inside the gap, got {}",
+        chunks[0].end
+    );
+}
+
+#[test]
+fn overlapping_segments_appear_once() {
+    let chunks = [
+        AudioChunk {
+            start: 0,
+            end: RATE * 10,
+        },
+        AudioChunk {
+            start: RATE * 8,
+            end: RATE * 18,
+        },
+    ];
+    let results = vec![
+        result(vec![segment(1.0, 2.0, " one"), segment(8.5, 9.5, " two")]),
+        result(vec![segment(0.5, 1.5, " two"), segment(4.0, 5.0, " three")]),
+    ];
+
+    let stitched = stitch_results(&chunks, results).unwrap();
+
+    assert_eq!(stitched.text, "one two three");
+    let times: Vec<(f32, f32)> = stitched.segments.iter().map(|s| (s.start, s.end)).collect();
+    assert_eq!(times, vec![(1.0, 2.0), (8.5, 9.5), (12.0, 13.0)]);
+}
+
+#[test]
+fn segments_without_leading_space_are_joined_with_spaces() {
+    let chunks = [AudioChunk {
+        start: 0,
+        end: RATE * 5,
+    }];
+    let results = vec![result(vec![
+        segment(0.0, 0.5, "hello"),
+        segment(0.5, 1.0, "world"),
+    ])];
+
+    assert_eq!(
+        stitch_results(&chunks, results).unwrap().text,
+        "hello world"
+    );
+}
+
+#[test]
+fn spacing_is_decided_per_segment() {
+    let chunks = [AudioChunk {
+        start: 0,
+        end: RATE * 5,
+    }];
+    let results = vec![result(vec![
+        segment(0.0, 0.5, " hello"),
+        segment(0.5, 1.0, "world"),
+        segment(1.0, 1.5, "again "),
+        segment(1.5, 2.0, "and"),
+    ])];
+
+    assert_eq!(
+        stitch_results(&chunks, results).unwrap().text,
+        "hello world again and"
+    );
+}
+
+#[test]
+fn text_without_segments_is_only_kept_outside_overlaps() {
+    let text_only = |text: &str| TranscriptionResult {
+        text: text.to_string(),
+        segments: Vec::new(),
+        language: None,
+    };
+    let apart = [
+        AudioChunk {
+            start: 0,
+            end: RATE * 10,
+        },
+        AudioChunk {
+            start: RATE * 10,
+            end: RATE * 20,
+        },
+    ];
+    let stitched = stitch_results(&apart, vec![text_only("one"), text_only("two")]).unwrap();
+    assert_eq!(stitched.text, "one two");
+
+    let overlapping = [
+        apart[0],
+        AudioChunk {
+            start: RATE * 8,
+            end: RATE * 18,
+        },
+    ];
+    let err = stitch_results(
+        &overlapping,
+        vec![result(vec![segment(1.0, 2.0, " one")]), text_only("two")],
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, TranscribeError::UnstitchableChunk(1)),
+        "{err:?}"
+    );
+
+    // Silent chunks have nothing to duplicate
+    let stitched = stitch_results(
+        &overlapping,
+        vec![result(vec![segment(1.0, 2.0, " one")]), text_only("")],
+    )
+    .unwrap();
+    assert_eq!(stitched.text, "one");
+}
+
+#[test]
+fn chunked_transcription_offsets_timestamps() -> Result<(), TranscribeError> {
+    let samples = vec![0.3; RATE * 65];
+    let params = ChunkingParams {
+        chunk_seconds: 30.0,
+        overlap_seconds: 0.0,
+        silence_search_seconds: 0.0,
+    };
+    let mut lengths = Vec::new();
+
+    let stitched = transcribe_chunked_with(&samples, &params, |chunk| {
+        lengths.push(chunk.len());
+        let duration = chunk.len() as f32 / RATE as f32;
+        Ok(result(vec![segment(0.0, duration, " chunk")]))
+    })?;
+
+    assert_eq!(lengths, vec![RATE * 30, RATE * 30, RATE * 5]);
+    assert_eq!(stitched.text, "chunk chunk chunk");
+    let starts: Vec<f32> = stitched.segments.iter().map(|s| s.start).collect();
+    assert_eq!(starts, vec![0.0, 30.0, 60.0]);
+    Ok(())
+}
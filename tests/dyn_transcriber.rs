@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use transcribe_rs::{
+    engines::{create_engine, EngineKind},
+    DynTranscriber, TimestampGranularity, TranscribeError, TranscribeOptions, TranscriptionEngine,
+    TranscriptionResult, TranscriptionTask,
+};
+
+/// Inference parameters recording the options they were built from.
+#[derive(Clone, Default)]
+struct EchoParams {
+    language: Option<String>,
+    granularity: TimestampGranularity,
+}
+
+impl From<&TranscribeOptions> for EchoParams {
+    fn from(options: &TranscribeOptions) -> Self {
+        Self {
+            language: options.language.clone(),
+            granularity: options.timestamp_granularity.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Engine that echoes the inference parameters it received as text.
+#[derive(Default)]
+struct EchoEngine {
+    loaded: bool,
+}
+
+impl TranscriptionEngine for EchoEngine {
+    type InferenceParams = EchoParams;
+    type ModelParams = ();
+
+    fn load_model_with_params(
+        &mut self,
+        _model_path: &Path,
+        _params: Self::ModelParams,
+    ) -> Result<(), TranscribeError> {
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn unload_model(&mut self) {
+        self.loaded = false;
+    }
+
+    fn transcribe_samples(
+        &mut self,
+        _samples: Vec<f32>,
+        params: Option<Self::InferenceParams>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        if !self.loaded {
+            return Err(TranscribeError::ModelNotLoaded);
+        }
+        let params = params.unwrap_or_default();
+        Ok(TranscriptionResult {
+            text: format!(
+                "{} {:?}",
+                params.language.as_deref().unwrap_or("auto"),
+                params.granularity
+            ),
+            segments: Vec::new(),
+            language: None,
+        })
+    }
+}
+
+#[test]
+fn engine_names_parse_case_insensitively() {
+    assert_eq!(
+        "whisper".parse::<EngineKind>().unwrap(),
+        EngineKind::Whisper
+    );
+    assert_eq!(
+        "Whisper".parse::<EngineKind>().unwrap(),
+        EngineKind::Whisper
+    );
+    #[cfg(feature = "parakeet")]
+    assert_eq!(
+        "PARAKEET".parse::<EngineKind>().unwrap(),
+        EngineKind::Parakeet
+    );
+
+    for kind in EngineKind::ALL {
+        assert_eq!(kind.to_string().parse::<EngineKind>().unwrap(), *kind);
+    }
+}
+
+#[test]
+fn unknown_engine_name_is_reported() {
+    match create_engine("wav2vec", Path::new("models/wav2vec")) {
+        Err(TranscribeError::UnknownEngine(name)) => assert_eq!(name, "wav2vec"),
+        Err(other) => panic!("expected unknown engine error, got {other:?}"),
+        Ok(_) => panic!("expected unknown engine error"),
+    }
+}
+
+#[test]
+fn factory_surfaces_model_loading_errors() {
+    let path = Path::new("does/not/exist/ggml-tiny.bin");
+    match create_engine("whisper", path) {
+        Err(TranscribeError::ModelFileMissing(missing)) => assert_eq!(missing, path),
+        Err(other) => panic!("expected missing model error, got {other:?}"),
+        Ok(_) => panic!("expected missing model error"),
+    }
+}
+
+#[test]
+fn boxed_engines_require_a_loaded_model() {
+    for kind in EngineKind::ALL {
+        let mut engine = kind.create();
+        let err = engine
+            .transcribe_samples(vec![0.0; 16_000], &TranscribeOptions::default())
+            .expect_err("transcription without a model should fail");
+        assert!(matches!(err, TranscribeError::ModelNotLoaded));
+    }
+}
+
+#[test]
+fn options_are_converted_to_engine_params() -> Result<(), TranscribeError> {
+    let mut engine: Box<dyn DynTranscriber> = Box::new(EchoEngine::default());
+    engine.load_model(Path::new("unused"))?;
+
+    let options = TranscribeOptions {
+        language: Some("de".to_string()),
+        timestamp_granularity: Some(TimestampGranularity::Word),
+        ..Default::default()
+    };
+    let result = engine.transcribe_samples(vec![0.0; 160], &options)?;
+    assert_eq!(result.text, "de Word");
+
+    engine.unload_model();
+    let err = engine
+        .transcribe_samples(vec![0.0; 160], &options)
+        .expect_err("unloaded engine should fail");
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+    Ok(())
+}
+
+#[test]
+fn unsupported_tasks_are_rejected() {
+    let mut engine: Box<dyn DynTranscriber> = Box::new(EchoEngine { loaded: true });
+    let options = TranscribeOptions {
+        task: TranscriptionTask::Translate,
+        ..Default::default()
+    };
+
+    assert!(!engine.supports_task(TranscriptionTask::Translate));
+    let err = engine
+        .transcribe_samples(vec![0.0; 16], &options)
+        .expect_err("translation is not supported");
+    assert!(matches!(
+        err,
+        TranscribeError::UnsupportedTask(TranscriptionTask::Translate)
+    ));
+
+    #[cfg(feature = "parakeet")]
+    {
+        let mut parakeet = EngineKind::Parakeet.create();
+        assert!(matches!(
+            parakeet.transcribe_samples(vec![0.0; 16], &options),
+            Err(TranscribeError::UnsupportedTask(
+                TranscriptionTask::Translate
+            ))
+        ));
+    }
+
+    let whisper = EngineKind::Whisper.create();
+    assert!(whisper.supports_task(TranscriptionTask::Translate));
+}
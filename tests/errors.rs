@@ -0,0 +1,159 @@
+use std::path::Path;
+
+#[cfg(feature = "parakeet")]
+use transcribe_rs::engines::parakeet::{
+    ParakeetEngine, ParakeetError, ParakeetModelBytes, ParakeetModelParams, SessionOptions,
+};
+use transcribe_rs::{
+    engines::whisper::{WhisperEngine, WhisperModelParams},
+    TranscribeError, TranscriptionEngine,
+};
+
+#[test]
+fn whisper_transcribe_without_model_reports_not_loaded() {
+    let mut engine = WhisperEngine::new();
+    let err = engine
+        .transcribe_samples(vec![0.0; 16_000], None)
+        .expect_err("transcription without a model should fail");
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+}
+
+#[test]
+fn whisper_missing_model_file_is_reported() {
+    let mut engine = WhisperEngine::new();
+    let path = Path::new("does/not/exist/ggml-tiny.bin");
+    match engine.load_model(path) {
+        Err(TranscribeError::ModelFileMissing(missing)) => assert_eq!(missing, path),
+        other => panic!("expected missing model error, got {other:?}"),
+    }
+}
+
+#[test]
+fn whisper_detect_language_without_model_reports_not_loaded() {
+    let mut engine = WhisperEngine::new();
+    let err = engine
+        .detect_language(&[0.0; 16_000], Some(2))
+        .expect_err("language detection without a model should fail");
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+}
+
+#[test]
+fn whisper_detect_language_rejects_zero_threads() {
+    let mut engine = WhisperEngine::new();
+    let err = engine
+        .detect_language(&[0.0; 16_000], Some(0))
+        .expect_err("zero threads should be rejected");
+    assert!(
+        matches!(err, TranscribeError::InvalidParameter(_)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn whisper_detect_language_rejects_empty_samples() {
+    let mut engine = WhisperEngine::new();
+    let err = engine
+        .detect_language(&[], None)
+        .expect_err("empty audio should be rejected");
+    assert!(
+        matches!(err, TranscribeError::UnsupportedAudioFormat { .. }),
+        "{err:?}"
+    );
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_transcribe_without_model_reports_not_loaded() {
+    let mut engine = ParakeetEngine::new();
+    let err = engine
+        .transcribe_samples(vec![0.0; 16_000], None)
+        .expect_err("transcription without a model should fail");
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_missing_model_directory_is_reported() {
+    let mut engine = ParakeetEngine::new();
+    let path = Path::new("does/not/exist/parakeet-v3");
+    match engine.load_model(path) {
+        Err(TranscribeError::ModelFileMissing(missing)) => assert_eq!(missing, path),
+        other => panic!("expected missing model error, got {other:?}"),
+    }
+}
+
+#[test]
+fn whisper_bytes_that_are_not_a_model_are_rejected() {
+    let mut engine = WhisperEngine::new();
+    for bytes in [&b""[..], b"not a GGML model"] {
+        let err = engine
+            .load_model_from_bytes(bytes, WhisperModelParams::default())
+            .expect_err("garbage bytes should not load");
+        assert!(matches!(err, TranscribeError::Whisper(_)), "{err:?}");
+    }
+
+    let err = engine
+        .transcribe_samples(vec![0.0; 16_000], None)
+        .expect_err("a failed load should leave the engine unloaded");
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_vocab_without_blank_token_is_rejected() {
+    let mut engine = ParakeetEngine::new();
+    let model = ParakeetModelBytes {
+        encoder: b"not onnx",
+        decoder_joint: b"not onnx",
+        preprocessor: b"not onnx",
+        vocab: "a 0\nb 1\n",
+    };
+    match engine.load_model_from_bytes(model, ParakeetModelParams::default()) {
+        Err(TranscribeError::Parakeet(ParakeetError::Io(err))) => {
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+        other => panic!("expected a vocabulary error, got {other:?}"),
+    }
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_graph_bytes_that_are_not_onnx_are_rejected() {
+    let mut engine = ParakeetEngine::new();
+    let model = ParakeetModelBytes {
+        encoder: b"not onnx",
+        decoder_joint: b"not onnx",
+        preprocessor: b"not onnx",
+        vocab: "<blk> 0\n",
+    };
+    let err = engine
+        .load_model_from_bytes(model, ParakeetModelParams::default())
+        .expect_err("garbage graphs should not load");
+    assert!(matches!(err, TranscribeError::Parakeet(_)), "{err:?}");
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_bytes_honour_session_options() {
+    let mut engine = ParakeetEngine::new();
+    let model = ParakeetModelBytes {
+        encoder: b"",
+        decoder_joint: b"",
+        preprocessor: b"",
+        vocab: "<blk> 0\n",
+    };
+    let params = ParakeetModelParams {
+        session: SessionOptions {
+            intra_threads: Some(0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = engine
+        .load_model_from_bytes(model, params)
+        .expect_err("invalid session options should be rejected");
+    assert!(
+        matches!(err, TranscribeError::InvalidParameter(_)),
+        "{err:?}"
+    );
+}
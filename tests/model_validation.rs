@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "parakeet")]
+use transcribe_rs::engines::parakeet::{self, ParakeetEngine, ParakeetModelParams};
+use transcribe_rs::{
+    engines::{whisper, ModelProblem},
+    TranscribeError, TranscriptionEngine,
+};
+
+#[test]
+fn whisper_accepts_ggml_header() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let model = temp_dir.path().join("ggml-tiny.bin");
+    fs::write(&model, [0x6c, 0x6d, 0x67, 0x67, 0, 0, 0, 0])?;
+
+    assert!(whisper::validate_model(&model).is_valid());
+    Ok(())
+}
+
+#[test]
+fn whisper_rejects_files_without_ggml_header() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let model = temp_dir.path().join("model.onnx");
+    fs::write(&model, b"not a whisper model")?;
+
+    let report = whisper::validate_model(&model);
+    assert!(matches!(
+        report.problems.as_slice(),
+        [ModelProblem::InvalidContents { path, .. }] if path == &model
+    ));
+
+    let mut engine = whisper::WhisperEngine::new();
+    match engine.load_model(&model) {
+        Err(TranscribeError::InvalidModel(invalid)) => assert_eq!(invalid, report),
+        other => panic!("expected invalid model error, got {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn whisper_reports_missing_file() {
+    let path = Path::new("does/not/exist/ggml-tiny.bin");
+    let report = whisper::validate_model(path);
+    assert_eq!(
+        report.problems,
+        vec![ModelProblem::Missing(path.to_path_buf())]
+    );
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_reports_every_missing_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let dir = temp_dir.path();
+    fs::write(dir.join("encoder-model.int8.onnx"), b"")?;
+    fs::write(dir.join("vocab.txt"), "\u{2581}the 0\n")?;
+
+    let report = parakeet::validate_model(dir);
+    assert_eq!(
+        report.problems,
+        vec![
+            ModelProblem::Missing(dir.join("decoder_joint-model.onnx")),
+            ModelProblem::Missing(dir.join("nemo128.onnx")),
+            ModelProblem::InvalidContents {
+                path: dir.join("vocab.txt"),
+                reason: "missing <blk> token".to_string(),
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_full_precision_load_requires_regular_graphs() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = tempfile::tempdir()?;
+    let dir = temp_dir.path();
+    fs::write(dir.join("encoder-model.int8.onnx"), b"")?;
+    fs::write(dir.join("decoder_joint-model.int8.onnx"), b"")?;
+    fs::write(dir.join("nemo128.onnx"), b"")?;
+    fs::write(dir.join("vocab.txt"), "<blk> 0\n")?;
+
+    assert!(parakeet::validate_model(dir).is_valid());
+
+    let mut engine = ParakeetEngine::new();
+    match engine.load_model_with_params(dir, ParakeetModelParams::fp32()) {
+        Err(TranscribeError::InvalidModel(report)) => assert_eq!(
+            report.problems,
+            vec![
+                ModelProblem::Missing(dir.join("encoder-model.onnx")),
+                ModelProblem::Missing(dir.join("decoder_joint-model.onnx")),
+            ]
+        ),
+        other => panic!("expected invalid model error, got {:?}", other.err()),
+    }
+    Ok(())
+}
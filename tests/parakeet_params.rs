@@ -0,0 +1,182 @@
+#![cfg(feature = "parakeet")]
+
+use std::path::{Path, PathBuf};
+
+use transcribe_rs::engines::parakeet::{
+    DecodingStrategy, ExecutionProvider, OptimizationLevel, ParakeetEngine,
+    ParakeetInferenceParams, ParakeetModelParams, QuantizationType, SessionOptions,
+    SessionProviders,
+};
+use transcribe_rs::{TranscribeError, TranscriptionEngine};
+
+#[test]
+fn zero_thread_counts_are_rejected() {
+    let missing = Path::new("models/missing-parakeet");
+    for session in [
+        SessionOptions {
+            intra_threads: Some(0),
+            ..Default::default()
+        },
+        SessionOptions {
+            inter_threads: Some(0),
+            ..Default::default()
+        },
+    ] {
+        let params = ParakeetModelParams {
+            session,
+            ..Default::default()
+        };
+        let err = ParakeetEngine::new()
+            .load_model_with_params(missing, params)
+            .unwrap_err();
+        assert!(
+            matches!(err, TranscribeError::InvalidParameter(_)),
+            "{err:?}"
+        );
+    }
+
+    let params = ParakeetModelParams {
+        session: SessionOptions {
+            intra_threads: Some(1),
+            inter_threads: Some(1),
+            optimization_level: OptimizationLevel::Level1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = ParakeetEngine::new()
+        .load_model_with_params(missing, params)
+        .unwrap_err();
+    assert!(
+        matches!(err, TranscribeError::ModelFileMissing(_)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn optimized_model_dir_must_not_be_a_file() {
+    let params = ParakeetModelParams {
+        session: SessionOptions {
+            optimized_model_dir: Some(PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/Cargo.toml"
+            ))),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = ParakeetEngine::new()
+        .load_model_with_params(Path::new("models/missing-parakeet"), params)
+        .unwrap_err();
+    assert!(
+        matches!(err, TranscribeError::InvalidParameter(_)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn empty_tdt_durations_are_rejected() {
+    let params = ParakeetModelParams {
+        tdt_durations: Vec::new(),
+        ..Default::default()
+    };
+    let err = ParakeetEngine::new()
+        .load_model_with_params(Path::new("models/missing-parakeet"), params)
+        .unwrap_err();
+    assert!(
+        matches!(err, TranscribeError::InvalidParameter(_)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn quantization_constructors_use_default_session_options() {
+    let params = ParakeetModelParams::int8();
+    assert_eq!(params.quantization, QuantizationType::Int8);
+    assert_eq!(params.session.intra_threads, None);
+
+    let params = ParakeetModelParams {
+        session: SessionOptions {
+            intra_threads: Some(2),
+            ..Default::default()
+        },
+        ..ParakeetModelParams::fp32()
+    };
+    assert_eq!(params.quantization, QuantizationType::FP32);
+    assert_eq!(params.session.intra_threads, Some(2));
+}
+
+#[test]
+fn primary_provider_is_the_first_registered() {
+    let mut providers = SessionProviders {
+        session: "encoder-model".to_string(),
+        registered: vec![ExecutionProvider::Cuda, ExecutionProvider::Cpu],
+        skipped: Vec::new(),
+    };
+    assert_eq!(providers.primary(), ExecutionProvider::Cuda);
+
+    providers.registered.clear();
+    assert_eq!(providers.primary(), ExecutionProvider::Cpu);
+
+    assert!(ParakeetEngine::new().execution_providers().is_none());
+}
+
+#[test]
+fn inference_defaults_to_greedy_decoding() {
+    assert_eq!(
+        ParakeetInferenceParams::default().decoding,
+        DecodingStrategy::Greedy
+    );
+    assert_eq!(
+        DecodingStrategy::beam_search(4),
+        DecodingStrategy::BeamSearch { beam_size: 4 }
+    );
+    assert_eq!(
+        DecodingStrategy::maes(4),
+        DecodingStrategy::Maes {
+            beam_size: 4,
+            expansion_beta: 2,
+            expansion_gamma: 2.3,
+        }
+    );
+}
+
+#[test]
+fn empty_beams_are_rejected_before_decoding() {
+    for decoding in [
+        DecodingStrategy::beam_search(0),
+        DecodingStrategy::maes(0),
+        DecodingStrategy::Maes {
+            beam_size: 4,
+            expansion_beta: 2,
+            expansion_gamma: -1.0,
+        },
+        DecodingStrategy::Maes {
+            beam_size: 4,
+            expansion_beta: 2,
+            expansion_gamma: f32::NAN,
+        },
+    ] {
+        let params = ParakeetInferenceParams {
+            decoding,
+            ..Default::default()
+        };
+        let err = ParakeetEngine::new()
+            .transcribe_samples(vec![0.0; 16000], Some(params))
+            .unwrap_err();
+        assert!(
+            matches!(err, TranscribeError::InvalidParameter(_)),
+            "{decoding:?}: {err:?}"
+        );
+    }
+
+    // Valid strategies get as far as the missing model
+    let params = ParakeetInferenceParams {
+        decoding: DecodingStrategy::maes(4),
+        ..Default::default()
+    };
+    let err = ParakeetEngine::new()
+        .transcribe_samples(vec![0.0; 16000], Some(params))
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::ModelNotLoaded), "{err:?}");
+}
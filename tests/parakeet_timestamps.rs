@@ -0,0 +1,68 @@
+#![cfg(feature = "parakeet")]
+
+use transcribe_rs::engines::parakeet::{convert_timestamps, TimestampedResult};
+use transcribe_rs::TimestampGranularity;
+
+fn result(end_timestamps: Vec<f32>) -> TimestampedResult {
+    TimestampedResult {
+        text: "Hi there".to_string(),
+        timestamps: vec![0.0, 0.32, 0.48],
+        end_timestamps,
+        tokens: vec![" Hi".to_string(), " the".to_string(), "re".to_string()],
+        probabilities: Vec::new(),
+        entropies: Vec::new(),
+    }
+}
+
+fn spans(segments: &[transcribe_rs::TranscriptionSegment]) -> Vec<(f32, f32)> {
+    segments.iter().map(|s| (s.start, s.end)).collect()
+}
+
+#[test]
+fn tdt_durations_give_token_end_times() {
+    let segments = convert_timestamps(&result(vec![0.16, 0.4, 0.64]), TimestampGranularity::Token);
+    assert_eq!(
+        spans(&segments),
+        vec![(0.0, 0.16), (0.32, 0.4), (0.48, 0.64)]
+    );
+
+    let words = convert_timestamps(&result(vec![0.16, 0.4, 0.64]), TimestampGranularity::Word);
+    assert_eq!(spans(&words), vec![(0.0, 0.16), (0.32, 0.64)]);
+}
+
+#[test]
+fn tokens_without_durations_end_at_the_next_token() {
+    let segments = convert_timestamps(&result(Vec::new()), TimestampGranularity::Token);
+    assert_eq!(
+        spans(&segments),
+        vec![(0.0, 0.32), (0.32, 0.48), (0.48, 0.53)]
+    );
+}
+
+#[test]
+fn token_probabilities_are_averaged_into_words_and_segments() {
+    let mut result = result(vec![0.16, 0.4, 0.64]);
+    result.probabilities = vec![0.9, 0.5, 0.7];
+    result.entropies = vec![0.1, 1.2, 0.6];
+
+    let tokens = convert_timestamps(&result, TimestampGranularity::Token);
+    let probabilities: Vec<_> = tokens.iter().map(|s| s.probability).collect();
+    assert_eq!(probabilities, vec![Some(0.9), Some(0.5), Some(0.7)]);
+
+    let words = convert_timestamps(&result, TimestampGranularity::Word);
+    let probabilities: Vec<_> = words.iter().map(|s| s.probability).collect();
+    assert_eq!(probabilities, vec![Some(0.9), Some(0.6)]);
+
+    let segments = convert_timestamps(&result, TimestampGranularity::Segment);
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].probability, Some(0.7));
+    let word_probabilities: Vec<_> = segments[0].words.iter().map(|w| w.probability).collect();
+    assert_eq!(word_probabilities, vec![Some(0.9), Some(0.6)]);
+}
+
+#[test]
+fn missing_probabilities_leave_confidence_empty() {
+    let segments = convert_timestamps(&result(Vec::new()), TimestampGranularity::Segment);
+    assert_eq!(segments[0].probability, None);
+    assert!(segments[0].words.iter().all(|w| w.probability.is_none()));
+}
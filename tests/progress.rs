@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[cfg(feature = "parakeet")]
+use transcribe_rs::engines::parakeet::{ParakeetEngine, ParakeetError, ParakeetInferenceParams};
+use transcribe_rs::{
+    engines::whisper::{WhisperEngine, WhisperInferenceParams},
+    CancellationToken, ProgressObserver, TranscribeError, TranscribeOptions, TranscriptionEngine,
+    TranscriptionSegment,
+};
+
+#[derive(Default)]
+struct Recorder {
+    percents: Mutex<Vec<f32>>,
+}
+
+impl ProgressObserver for Recorder {
+    fn on_progress(&self, percent: f32) {
+        self.percents.lock().unwrap().push(percent);
+    }
+}
+
+#[test]
+fn cancellation_is_shared_between_clones() {
+    let token = CancellationToken::new();
+    let worker_token = token.clone();
+    assert!(!worker_token.is_cancelled());
+
+    thread::spawn(move || token.cancel()).join().unwrap();
+
+    assert!(worker_token.is_cancelled());
+}
+
+#[test]
+fn observer_methods_default_to_no_ops() {
+    struct Silent;
+    impl ProgressObserver for Silent {}
+
+    let observer: Arc<dyn ProgressObserver> = Arc::new(Silent);
+    observer.on_progress(50.0);
+    observer.on_segment(&TranscriptionSegment {
+        start: 0.0,
+        end: 1.0,
+        text: "hello".to_string(),
+        words: Vec::new(),
+        probability: None,
+    });
+}
+
+#[test]
+fn options_forward_observer_and_token_to_engine_params() {
+    let recorder = Arc::new(Recorder::default());
+    let token = CancellationToken::new();
+    let options = TranscribeOptions {
+        progress: Some(recorder.clone()),
+        cancellation: Some(token.clone()),
+        ..Default::default()
+    };
+
+    let params = WhisperInferenceParams::from(&options);
+    params.progress.as_ref().unwrap().on_progress(42.0);
+    token.cancel();
+
+    assert_eq!(*recorder.percents.lock().unwrap(), vec![42.0]);
+    assert!(params.cancellation.unwrap().is_cancelled());
+    assert!(format!("{options:?}").contains("ProgressObserver"));
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_cancellation_maps_to_cancelled_error() {
+    let err = TranscribeError::from(ParakeetError::Cancelled);
+    assert!(matches!(err, TranscribeError::Cancelled));
+
+    let err = TranscribeError::from(ParakeetError::InputNotFound("x".to_string()));
+    assert!(matches!(err, TranscribeError::Parakeet(_)));
+}
+
+#[test]
+fn whisper_pre_cancelled_token_yields_cancelled() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let params = WhisperInferenceParams {
+        cancellation: Some(token),
+        ..Default::default()
+    };
+
+    let err = WhisperEngine::new()
+        .transcribe_samples(vec![0.0; 16_000], Some(params))
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::Cancelled));
+}
+
+#[cfg(feature = "parakeet")]
+#[test]
+fn parakeet_pre_cancelled_token_yields_cancelled() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let params = ParakeetInferenceParams {
+        cancellation: Some(token),
+        ..Default::default()
+    };
+
+    let err = ParakeetEngine::new()
+        .transcribe_samples(vec![0.0; 16_000], Some(params))
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::Cancelled));
+}
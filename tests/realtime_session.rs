@@ -1,10 +1,8 @@
 use std::{cell::RefCell, io, rc::Rc};
 
 use transcribe_rs::{
-    realtime::{
-        InboundMessage, OutboundMessage, RealtimeSession, RealtimeTranscriber, SerializableSegment,
-    },
-    TranscriptionResult, TranscriptionSegment,
+    realtime::{InboundMessage, OutboundMessage, RealtimeSession, RealtimeTranscriber},
+    TranscribeError, TranscriptionResult, TranscriptionSegment,
 };
 
 struct MockTranscriber {
@@ -32,12 +30,12 @@ impl RealtimeTranscriber for MockTranscriber {
         &mut self,
         _samples: Vec<f32>,
         language: Option<&str>,
-    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+    ) -> Result<TranscriptionResult, TranscribeError> {
         self.calls
             .borrow_mut()
             .push(language.map(|value| value.to_string()));
         if self.responses.is_empty() {
-            return Err(Box::new(io::Error::new(
+            return Err(TranscribeError::Io(io::Error::new(
                 io::ErrorKind::UnexpectedEof,
                 "no mock response configured",
             )));
@@ -45,7 +43,7 @@ impl RealtimeTranscriber for MockTranscriber {
 
         match self.responses.remove(0) {
             Ok(result) => Ok(result),
-            Err(err) => Err(Box::new(io::Error::new(err.kind(), err.to_string()))),
+            Err(err) => Err(TranscribeError::Io(err)),
         }
     }
 }
@@ -57,12 +55,15 @@ fn make_result(text: &str, segments: &[(&str, f32, f32)]) -> TranscriptionResult
             start: *start,
             end: *end,
             text: content.to_string(),
+            words: Vec::new(),
+            probability: None,
         })
         .collect();
 
     TranscriptionResult {
         text: text.to_string(),
         segments,
+        language: None,
     }
 }
 
@@ -83,11 +84,14 @@ fn chunk_emits_transcript_when_text_changes() {
         messages[0],
         OutboundMessage::Transcript {
             text: "hello world".to_string(),
-            segments: vec![SerializableSegment {
+            segments: vec![TranscriptionSegment {
                 start: 0.0,
                 end: 1.5,
                 text: "hello world".to_string(),
+                words: Vec::new(),
+                probability: None,
             }],
+            language: None,
         }
     );
 }
@@ -170,7 +174,7 @@ fn flush_replays_last_transcript() {
 
 #[test]
 fn errors_are_wrapped_into_error_messages() {
-    let responses = vec![Err(io::Error::new(io::ErrorKind::Other, "mock failure"))];
+    let responses = vec![Err(io::Error::other("mock failure"))];
     let (transcriber, _) = MockTranscriber::with_responses(responses);
     let mut session = RealtimeSession::new(transcriber, None);
 
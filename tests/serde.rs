@@ -0,0 +1,154 @@
+#![cfg(feature = "serde")]
+
+use transcribe_rs::{TranscriptionResult, TranscriptionSegment, WordTiming};
+
+fn sample_result() -> TranscriptionResult {
+    TranscriptionResult {
+        text: "hello world".to_string(),
+        segments: vec![
+            TranscriptionSegment {
+                start: 0.0,
+                end: 0.5,
+                text: "hello".to_string(),
+                words: Vec::new(),
+                probability: None,
+            },
+            TranscriptionSegment {
+                start: 0.5,
+                end: 1.25,
+                text: " world".to_string(),
+                words: vec![WordTiming {
+                    text: "world".to_string(),
+                    start: 0.5,
+                    end: 1.25,
+                    probability: Some(0.75),
+                }],
+                probability: Some(0.75),
+            },
+        ],
+        language: Some("en".to_string()),
+    }
+}
+
+#[test]
+fn result_serializes_to_stable_json_shape() {
+    let json = serde_json::to_value(sample_result()).expect("result should serialize");
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "text": "hello world",
+            "segments": [
+                { "start": 0.0, "end": 0.5, "text": "hello" },
+                {
+                    "start": 0.5,
+                    "end": 1.25,
+                    "text": " world",
+                    "words": [
+                        { "text": "world", "start": 0.5, "end": 1.25, "probability": 0.75 },
+                    ],
+                    "probability": 0.75,
+                },
+            ],
+            "language": "en",
+        })
+    );
+}
+
+#[test]
+fn result_round_trips_through_json() {
+    let result = sample_result();
+    let json = serde_json::to_string(&result).expect("result should serialize");
+    let decoded: TranscriptionResult = serde_json::from_str(&json).expect("result should parse");
+
+    assert_eq!(decoded, result);
+}
+
+#[test]
+fn unknown_fields_are_ignored_when_deserializing() {
+    let json = r#"{
+        "text": "hi",
+        "segments": [{ "start": 0.0, "end": 0.2, "text": "hi", "speaker": "A" }],
+        "model": "future-field"
+    }"#;
+
+    let decoded: TranscriptionResult = serde_json::from_str(json).expect("result should parse");
+    assert_eq!(decoded.segments.len(), 1);
+    assert_eq!(decoded.segments[0].text, "hi");
+}
+
+#[test]
+fn words_without_probability_omit_the_field() {
+    let word = WordTiming {
+        text: "hi".to_string(),
+        start: 0.0,
+        end: 0.5,
+        probability: None,
+    };
+
+    let json = serde_json::to_value(&word).expect("word should serialize");
+    assert_eq!(
+        json,
+        serde_json::json!({ "text": "hi", "start": 0.0, "end": 0.5 })
+    );
+}
+
+/// Field names of a JSON object, sorted.
+fn keys(value: &serde_json::Value) -> Vec<String> {
+    let mut keys: Vec<String> = value
+        .as_object()
+        .expect("expected a JSON object")
+        .keys()
+        .cloned()
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Check that `full` (every optional field set) uses exactly the properties
+/// of `schema`, and `minimal` (none set) exactly its required fields.
+fn assert_matches_schema(
+    schema: &serde_json::Value,
+    full: &serde_json::Value,
+    minimal: &serde_json::Value,
+) {
+    let mut required: Vec<String> = schema["required"]
+        .as_array()
+        .expect("schema should list required fields")
+        .iter()
+        .map(|field| field.as_str().unwrap().to_string())
+        .collect();
+    required.sort();
+
+    assert_eq!(keys(&schema["properties"]), keys(full));
+    assert_eq!(required, keys(minimal));
+}
+
+#[test]
+fn schema_matches_serialized_fields() {
+    let schema: serde_json::Value =
+        serde_json::from_str(include_str!("../docs/transcription-result.schema.json"))
+            .expect("schema should be valid JSON");
+
+    let full = serde_json::to_value(sample_result()).unwrap();
+    let minimal = serde_json::to_value(TranscriptionResult {
+        text: String::new(),
+        segments: Vec::new(),
+        language: None,
+    })
+    .unwrap();
+    assert_matches_schema(&schema, &full, &minimal);
+
+    let segments = &schema["$defs"]["TranscriptionSegment"];
+    assert_matches_schema(segments, &full["segments"][1], &full["segments"][0]);
+
+    let words = &schema["$defs"]["WordTiming"];
+    let minimal_word = serde_json::to_value(WordTiming {
+        text: String::new(),
+        start: 0.0,
+        end: 0.0,
+        probability: None,
+    })
+    .unwrap();
+    assert_matches_schema(words, &full["segments"][1]["words"][0], &minimal_word);
+}
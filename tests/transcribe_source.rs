@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::path::Path;
+
+use transcribe_rs::chunking::{transcribe_chunked, ChunkingParams};
+use transcribe_rs::{
+    audio::BufferSource, TranscribeError, TranscriptionEngine, TranscriptionResult,
+    TranscriptionSegment, SOURCE_WINDOW_SECONDS,
+};
+
+/// Engine that reports the length of each window it is asked to transcribe.
+#[derive(Default)]
+struct WindowLengthEngine {
+    windows: Vec<usize>,
+}
+
+impl TranscriptionEngine for WindowLengthEngine {
+    type InferenceParams = ();
+    type ModelParams = ();
+
+    fn load_model_with_params(
+        &mut self,
+        _model_path: &Path,
+        _params: Self::ModelParams,
+    ) -> Result<(), TranscribeError> {
+        Ok(())
+    }
+
+    fn unload_model(&mut self) {}
+
+    fn transcribe_samples(
+        &mut self,
+        samples: Vec<f32>,
+        _params: Option<Self::InferenceParams>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.windows.push(samples.len());
+        let text = format!("window{}", self.windows.len());
+        Ok(TranscriptionResult {
+            text: text.clone(),
+            segments: vec![TranscriptionSegment {
+                start: 1.0,
+                end: 2.0,
+                text,
+                words: Vec::new(),
+                probability: None,
+            }],
+            language: Some("en".to_string()),
+        })
+    }
+}
+
+#[test]
+fn transcribe_source_processes_overlapping_chunks_incrementally() -> Result<(), Box<dyn Error>> {
+    let window_len = (SOURCE_WINDOW_SECONDS * 16_000.0) as usize;
+    let overlap = ChunkingParams::default().overlap_seconds as usize * 16_000;
+    let total = window_len * 2 + 8_000;
+    let mut source = BufferSource::new(vec![0.0; total], 1_600);
+    let mut engine = WindowLengthEngine::default();
+
+    let result = engine.transcribe_source(&mut source, None)?;
+
+    // Silent input is cut at the nominal chunk ends, each chunk after the
+    // first starting `overlap` before the previous cut
+    let last = total - 2 * (window_len - overlap);
+    assert_eq!(engine.windows, vec![window_len, window_len, last]);
+    assert_eq!(result.text, "window1 window2 window3");
+
+    let starts: Vec<f32> = result.segments.iter().map(|s| s.start).collect();
+    let step = (window_len - overlap) as f32 / 16_000.0;
+    assert_eq!(starts, vec![1.0, 1.0 + step, 1.0 + 2.0 * step]);
+    assert_eq!(result.language.as_deref(), Some("en"));
+
+    Ok(())
+}
+
+#[test]
+fn transcribe_source_matches_chunked_transcription() -> Result<(), Box<dyn Error>> {
+    // Bursts of noise separated by short pauses, so chunks are cut early
+    let samples: Vec<f32> = (0..16_000usize * 95)
+        .map(|i| {
+            if (i / 16_000) % 7 == 6 {
+                0.0
+            } else {
+                ((i * 7919) % 200) as f32 / 1000.0
+            }
+        })
+        .collect();
+
+    let mut streamed = WindowLengthEngine::default();
+    let mut source = BufferSource::new(samples.clone(), 1_000);
+    let streamed_result = streamed.transcribe_source(&mut source, None)?;
+
+    let mut chunked = WindowLengthEngine::default();
+    let chunked_result =
+        transcribe_chunked(&mut chunked, &samples, None, &ChunkingParams::default())?;
+
+    assert!(streamed.windows.len() > 3);
+    assert_eq!(streamed.windows, chunked.windows);
+    assert_eq!(streamed_result, chunked_result);
+
+    Ok(())
+}
+
+#[test]
+fn transcribe_source_handles_empty_source() -> Result<(), Box<dyn Error>> {
+    let mut source = BufferSource::new(Vec::new(), 1_600);
+    let mut engine = WindowLengthEngine::default();
+
+    let result = engine.transcribe_source(&mut source, None)?;
+
+    assert!(engine.windows.is_empty());
+    assert!(result.text.is_empty());
+    assert!(result.segments.is_empty());
+    assert_eq!(result.language, None);
+
+    Ok(())
+}
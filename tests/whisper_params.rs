@@ -0,0 +1,221 @@
+use std::path::Path;
+
+use transcribe_rs::engines::whisper::{
+    WhisperDtwPreset, WhisperEngine, WhisperInferenceParams, WhisperModelParams,
+    WhisperSamplingStrategy, PREVIOUS_TEXT_MAX_CHARS,
+};
+use transcribe_rs::{
+    TimestampGranularity, TranscribeError, TranscribeOptions, TranscriptionEngine,
+    TranscriptionTask,
+};
+
+#[test]
+fn default_params_keep_beam_search_with_three_beams() {
+    let params = WhisperInferenceParams::default();
+    assert_eq!(
+        params.sampling_strategy,
+        WhisperSamplingStrategy::BeamSearch {
+            beam_size: 3,
+            patience: -1.0
+        }
+    );
+    assert_eq!(params.temperature, 0.0);
+    assert_eq!(params.temperature_inc, 0.2);
+}
+
+#[test]
+fn sampling_strategy_constructors() {
+    assert_eq!(
+        WhisperSamplingStrategy::greedy(),
+        WhisperSamplingStrategy::Greedy { best_of: 5 }
+    );
+    assert_eq!(
+        WhisperSamplingStrategy::beam_search(5),
+        WhisperSamplingStrategy::BeamSearch {
+            beam_size: 5,
+            patience: -1.0
+        }
+    );
+}
+
+#[test]
+fn previous_text_extends_the_initial_prompt() {
+    let params = WhisperInferenceParams::default();
+    assert_eq!(params.initial_prompt, None);
+    assert!(params.no_context);
+
+    let next = params.with_previous_text("  first chunk ");
+    assert_eq!(next.initial_prompt.as_deref(), Some("first chunk"));
+
+    let primed = WhisperInferenceParams {
+        initial_prompt: Some("Glossary: transcribe-rs.".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        primed.with_previous_text("").initial_prompt.as_deref(),
+        Some("Glossary: transcribe-rs.")
+    );
+    assert_eq!(
+        primed
+            .with_previous_text("second")
+            .initial_prompt
+            .as_deref(),
+        Some("Glossary: transcribe-rs. second")
+    );
+}
+
+#[test]
+fn long_previous_text_is_truncated_so_the_initial_prompt_survives() {
+    let primed = WhisperInferenceParams {
+        initial_prompt: Some("Glossary: transcribe-rs.".to_string()),
+        ..Default::default()
+    };
+    let previous_text = (0..1_000)
+        .map(|i| format!("word{i}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let prompt = primed
+        .with_previous_text(&previous_text)
+        .initial_prompt
+        .unwrap();
+    let tail = prompt.strip_prefix("Glossary: transcribe-rs. ").unwrap();
+    assert!(tail.chars().count() <= PREVIOUS_TEXT_MAX_CHARS);
+    assert!(tail.chars().count() > PREVIOUS_TEXT_MAX_CHARS - 10);
+    assert!(tail.starts_with("word"), "cut inside a word: {tail:?}");
+    assert!(previous_text.ends_with(tail));
+
+    // A single long word is cut on a character boundary.
+    let unbroken = "é".repeat(PREVIOUS_TEXT_MAX_CHARS * 2);
+    let prompt = primed.with_previous_text(&unbroken).initial_prompt.unwrap();
+    assert_eq!(
+        prompt,
+        format!(
+            "Glossary: transcribe-rs. {}",
+            "é".repeat(PREVIOUS_TEXT_MAX_CHARS)
+        )
+    );
+}
+
+#[test]
+fn task_defaults_to_transcribe_and_follows_options() {
+    assert_eq!(
+        WhisperInferenceParams::default().task,
+        TranscriptionTask::Transcribe
+    );
+
+    let options = TranscribeOptions {
+        task: TranscriptionTask::Translate,
+        ..Default::default()
+    };
+    assert_eq!(
+        WhisperInferenceParams::from(&options).task,
+        TranscriptionTask::Translate
+    );
+}
+
+#[test]
+fn granularity_defaults_to_segments_and_follows_options() {
+    let params = WhisperInferenceParams::default();
+    assert_eq!(params.timestamp_granularity, TimestampGranularity::Segment);
+    assert!(!params.word_timestamps);
+
+    assert_eq!(
+        WhisperInferenceParams::from(&TranscribeOptions::default()).timestamp_granularity,
+        TimestampGranularity::Segment
+    );
+
+    for granularity in [TimestampGranularity::Word, TimestampGranularity::Token] {
+        let options = TranscribeOptions {
+            timestamp_granularity: Some(granularity.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            WhisperInferenceParams::from(&options).timestamp_granularity,
+            granularity
+        );
+    }
+}
+
+#[test]
+fn zero_limits_are_rejected_before_inference() {
+    let zeroed = [
+        (
+            "max_segment_len",
+            WhisperInferenceParams {
+                max_segment_len: Some(0),
+                ..Default::default()
+            },
+        ),
+        (
+            "max_tokens",
+            WhisperInferenceParams {
+                max_tokens: Some(0),
+                ..Default::default()
+            },
+        ),
+        (
+            "n_threads",
+            WhisperInferenceParams {
+                n_threads: Some(0),
+                ..Default::default()
+            },
+        ),
+    ];
+    for (name, params) in zeroed {
+        match WhisperEngine::new().transcribe_samples(vec![0.0; 160], Some(params)) {
+            Err(TranscribeError::InvalidParameter(reason)) => assert!(reason.contains(name)),
+            other => panic!("expected {name} to be rejected, got {other:?}"),
+        }
+    }
+
+    let limited = WhisperInferenceParams {
+        max_segment_len: Some(42),
+        max_tokens: Some(1),
+        n_threads: Some(1),
+        ..Default::default()
+    };
+    let err = WhisperEngine::new()
+        .transcribe_samples(vec![0.0; 160], Some(limited))
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::ModelNotLoaded));
+}
+
+#[test]
+fn gpu_defaults_follow_whisper_rs() {
+    let params = WhisperModelParams::default();
+    assert_eq!(
+        params.use_gpu,
+        whisper_rs::WhisperContextParameters::default().use_gpu
+    );
+    assert_eq!(params.gpu_device, 0);
+    assert!(!params.flash_attn);
+}
+
+#[test]
+fn flash_attention_with_dtw_is_rejected() {
+    let conflicting = WhisperModelParams {
+        flash_attn: true,
+        dtw_preset: Some(WhisperDtwPreset::Tiny),
+        ..Default::default()
+    };
+    let missing = Path::new("models/missing.bin");
+
+    let err = WhisperEngine::new()
+        .load_model_with_params(missing, conflicting.clone())
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::InvalidParameter(_)));
+    let err = WhisperEngine::new()
+        .load_model_from_bytes(&[], conflicting)
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::InvalidParameter(_)));
+
+    let flash_only = WhisperModelParams {
+        flash_attn: true,
+        ..Default::default()
+    };
+    let err = WhisperEngine::new()
+        .load_model_with_params(missing, flash_only)
+        .unwrap_err();
+    assert!(matches!(err, TranscribeError::ModelFileMissing(_)));
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use transcribe_rs::{
+    engines::whisper::{WhisperModelParams, WhisperPool},
+    TranscribeError,
+};
+
+#[test]
+fn pool_can_be_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<WhisperPool>();
+}
+
+#[test]
+fn pool_missing_model_file_is_reported() {
+    let path = Path::new("does/not/exist/ggml-tiny.bin");
+    match WhisperPool::new(path, 2) {
+        Err(TranscribeError::ModelFileMissing(missing)) => assert_eq!(missing, path),
+        Err(other) => panic!("expected missing model error, got {other:?}"),
+        Ok(_) => panic!("expected missing model error"),
+    }
+}
+
+#[test]
+fn pool_rejects_bytes_that_are_not_a_model() {
+    for bytes in [&b""[..], b"RIFF\0\0\0\0WAVEfmt "] {
+        match WhisperPool::from_bytes(bytes, WhisperModelParams::default(), 2) {
+            Err(TranscribeError::Whisper(_)) => {}
+            Err(other) => panic!("expected a Whisper error, got {other:?}"),
+            Ok(_) => panic!("expected a Whisper error"),
+        }
+    }
+}